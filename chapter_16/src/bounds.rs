@@ -0,0 +1,320 @@
+use crate::{Matrix, Point, Ray, EPSILON};
+
+/// An axis-aligned bounding box, the smallest box lined up with the `x`, `y`,
+/// and `z` axes that contains a shape. Testing a [`Ray`] against the box is
+/// much cheaper than intersecting the shape, a ray that misses the box can't
+/// hit the shape inside of it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Bounds {
+    /// Corner of the box with the smallest coordinates.
+    pub min: Point,
+    /// Corner of the box with the largest coordinates.
+    pub max: Point,
+}
+
+impl Bounds {
+    /// Create a box with the corners `min` and `max`.
+    pub fn new(min: Point, max: Point) -> Bounds {
+        Bounds { min, max }
+    }
+
+    /// A box containing nothing, adding a point or another box to it gives
+    /// a box around just that point or box.
+    pub fn empty() -> Bounds {
+        Bounds {
+            min: Point::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            max: Point::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        }
+    }
+
+    /// A box containing everything, for shapes such as planes that go on
+    /// forever.
+    pub fn infinite() -> Bounds {
+        Bounds {
+            min: Point::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+            max: Point::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+        }
+    }
+
+    /// The box grown to contain the `point`.
+    pub fn add_point(self, point: Point) -> Bounds {
+        Bounds {
+            min: Point::new(
+                self.min.x.min(point.x),
+                self.min.y.min(point.y),
+                self.min.z.min(point.z),
+            ),
+            max: Point::new(
+                self.max.x.max(point.x),
+                self.max.y.max(point.y),
+                self.max.z.max(point.z),
+            ),
+        }
+    }
+
+    /// The box grown to contain the `other` box.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Bounds, Point};
+    ///
+    /// let a = Bounds::new(Point::new(-5.0, -2.0, 0.0), Point::new(7.0, 4.0, 4.0));
+    /// let b = Bounds::new(Point::new(8.0, -7.0, -2.0), Point::new(14.0, 2.0, 8.0));
+    /// let c = a.merge(b);
+    ///
+    /// assert_eq!(c.min, Point::new(-5.0, -7.0, -2.0));
+    /// assert_eq!(c.max, Point::new(14.0, 4.0, 8.0));
+    /// ```
+    pub fn merge(self, other: Bounds) -> Bounds {
+        if other.is_empty() {
+            return self;
+        }
+        self.add_point(other.min).add_point(other.max)
+    }
+
+    /// Check if the box contains nothing.
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    /// Check if the box goes on forever along any axis.
+    pub fn is_infinite(&self) -> bool {
+        !(self.min.x.is_finite()
+            && self.min.y.is_finite()
+            && self.min.z.is_finite()
+            && self.max.x.is_finite()
+            && self.max.y.is_finite()
+            && self.max.z.is_finite())
+    }
+
+    /// Check if the `point` is inside of the box or on its surface.
+    pub fn contains_point(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    /// The box around the eight corners of `self` transformed by the matrix
+    /// `m`, such as the bounds of a shape in the space of its parent. A box
+    /// that goes on forever stays infinite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Bounds, Point, Transformation};
+    /// use std::f64::consts::PI;
+    ///
+    /// let b = Bounds::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0));
+    /// let m = Transformation::new().rotate_y(PI / 4.0).rotate_x(PI / 4.0).build();
+    /// let t = b.transform(m);
+    ///
+    /// assert_eq!(t.min, Point::new(-1.41421, -1.70711, -1.70711));
+    /// assert_eq!(t.max, Point::new(1.41421, 1.70711, 1.70711));
+    /// ```
+    pub fn transform(&self, m: Matrix) -> Bounds {
+        if self.is_empty() {
+            return *self;
+        }
+        if self.is_infinite() {
+            return Bounds::infinite();
+        }
+
+        let (min, max) = (self.min, self.max);
+        [
+            Point::new(min.x, min.y, min.z),
+            Point::new(min.x, min.y, max.z),
+            Point::new(min.x, max.y, min.z),
+            Point::new(min.x, max.y, max.z),
+            Point::new(max.x, min.y, min.z),
+            Point::new(max.x, min.y, max.z),
+            Point::new(max.x, max.y, min.z),
+            Point::new(max.x, max.y, max.z),
+        ]
+        .iter()
+        .fold(Bounds::empty(), |b, p| b.add_point(m * *p))
+    }
+
+    /// Check if the line along the ray `r` passes through the box, grown by
+    /// [`EPSILON`] so a ray just touching a shape inside is not missed. The
+    /// line is tested in both directions, a box behind the origin of the ray
+    /// is still hit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Bounds, Point, Ray, Vector};
+    ///
+    /// let b = Bounds::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0));
+    ///
+    /// assert!(b.intersects(Ray::new(Point::new(5.0, 0.5, 0.0), Vector::new(-1.0, 0.0, 0.0))));
+    /// assert!(!b.intersects(Ray::new(Point::new(5.0, 2.0, 0.0), Vector::new(-1.0, 0.0, 0.0))));
+    /// ```
+    pub fn intersects(&self, r: Ray) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        // The range of `t` where the line is between the two sides of the box
+        // along one axis, `None` when the line is parallel to the sides and
+        // outside of them.
+        let axis = |origin: f64, direction: f64, min: f64, max: f64| {
+            let (min, max) = (min - EPSILON, max + EPSILON);
+            if direction == 0.0 {
+                return (min..=max)
+                    .contains(&origin)
+                    .then_some((f64::NEG_INFINITY, f64::INFINITY));
+            }
+
+            let tmin = (min - origin) / direction;
+            let tmax = (max - origin) / direction;
+            if tmin > tmax {
+                Some((tmax, tmin))
+            } else {
+                Some((tmin, tmax))
+            }
+        };
+
+        let ranges = [
+            axis(r.origin.x, r.direction.x, self.min.x, self.max.x),
+            axis(r.origin.y, r.direction.y, self.min.y, self.max.y),
+            axis(r.origin.z, r.direction.z, self.min.z, self.max.z),
+        ];
+
+        match ranges {
+            [Some((xmin, xmax)), Some((ymin, ymax)), Some((zmin, zmax))] => {
+                // `f64::max` and `f64::min` ignore the NaN of the line starting
+                // on an infinite side.
+                xmin.max(ymin).max(zmin) <= xmax.min(ymax).min(zmax)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Transformation, Vector};
+
+    #[test]
+    fn an_empty_box_grows_to_contain_points() {
+        let b = Bounds::empty()
+            .add_point(Point::new(-5.0, 2.0, 0.0))
+            .add_point(Point::new(7.0, 0.0, -3.0));
+
+        assert!(!b.is_empty());
+        assert_eq!(b.min, Point::new(-5.0, 0.0, -3.0));
+        assert_eq!(b.max, Point::new(7.0, 2.0, 0.0));
+        assert!(Bounds::empty().is_empty());
+    }
+
+    #[test]
+    fn checking_if_a_box_contains_a_point() {
+        let b = Bounds::new(Point::new(5.0, -2.0, 0.0), Point::new(11.0, 4.0, 7.0));
+
+        assert!(b.contains_point(Point::new(5.0, -2.0, 0.0)));
+        assert!(b.contains_point(Point::new(8.0, 1.0, 3.0)));
+        assert!(!b.contains_point(Point::new(3.0, 0.0, 3.0)));
+        assert!(!b.contains_point(Point::new(8.0, -4.0, 3.0)));
+        assert!(!b.contains_point(Point::new(8.0, 1.0, 8.0)));
+    }
+
+    #[test]
+    fn transforming_an_infinite_box_stays_infinite() {
+        let b = Bounds::new(
+            Point::new(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Point::new(f64::INFINITY, 0.0, f64::INFINITY),
+        );
+        let t = b.transform(Transformation::new().translate(0.0, 2.0, 0.0).build());
+
+        assert!(t.is_infinite());
+        assert_eq!(t.min.y, f64::NEG_INFINITY);
+        assert_eq!(t.max.y, f64::INFINITY);
+        assert!(Bounds::empty()
+            .transform(Transformation::new().scale(2.0, 2.0, 2.0).build())
+            .is_empty());
+    }
+
+    #[test]
+    fn intersecting_a_ray_with_a_box() {
+        let b = Bounds::new(Point::new(5.0, -2.0, 0.0), Point::new(11.0, 4.0, 7.0));
+        let data = vec![
+            (
+                Point::new(15.0, 1.0, 2.0),
+                Vector::new(-1.0, 0.0, 0.0),
+                true,
+            ),
+            (
+                Point::new(-5.0, -1.0, 4.0),
+                Vector::new(1.0, 0.0, 0.0),
+                true,
+            ),
+            (Point::new(7.0, 6.0, 5.0), Vector::new(0.0, -1.0, 0.0), true),
+            (Point::new(9.0, -5.0, 6.0), Vector::new(0.0, 1.0, 0.0), true),
+            (
+                Point::new(8.0, 2.0, 12.0),
+                Vector::new(0.0, 0.0, -1.0),
+                true,
+            ),
+            (Point::new(6.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0), true),
+            (Point::new(8.0, 1.0, 3.5), Vector::new(0.0, 0.0, 1.0), true),
+            (
+                Point::new(9.0, -1.0, -8.0),
+                Vector::new(2.0, 4.0, 6.0),
+                false,
+            ),
+            (
+                Point::new(8.0, 3.0, -4.0),
+                Vector::new(6.0, 2.0, 4.0),
+                false,
+            ),
+            (
+                Point::new(9.0, -1.0, -2.0),
+                Vector::new(4.0, 6.0, 2.0),
+                false,
+            ),
+            (
+                Point::new(4.0, 0.0, 9.0),
+                Vector::new(0.0, 0.0, -1.0),
+                false,
+            ),
+            (
+                Point::new(8.0, 6.0, -1.0),
+                Vector::new(0.0, -1.0, 0.0),
+                false,
+            ),
+            (
+                Point::new(12.0, 5.0, 4.0),
+                Vector::new(-1.0, 0.0, 0.0),
+                false,
+            ),
+        ];
+
+        for (origin, direction, expected) in data {
+            let r = Ray::new(origin, direction.normalize());
+            assert_eq!(b.intersects(r), expected);
+        }
+    }
+
+    #[test]
+    fn intersecting_a_ray_with_an_infinite_box() {
+        let plane = Bounds::new(
+            Point::new(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Point::new(f64::INFINITY, 0.0, f64::INFINITY),
+        );
+
+        assert!(plane.intersects(Ray::new(
+            Point::new(0.0, 1.0, 0.0),
+            Vector::new(0.0, -1.0, 0.0)
+        )));
+        assert!(!plane.intersects(Ray::new(
+            Point::new(0.0, 1.0, 0.0),
+            Vector::new(0.0, 0.0, 1.0)
+        )));
+        assert!(Bounds::infinite().intersects(Ray::new(
+            Point::new(0.0, 0.0, 0.0),
+            Vector::new(0.0, 0.0, 1.0)
+        )));
+    }
+}
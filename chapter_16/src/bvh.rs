@@ -0,0 +1,185 @@
+use crate::{Bounds, Ray};
+
+// Most objects kept in one leaf of the hierarchy.
+const LEAF_SIZE: usize = 4;
+
+/// A bounding volume hierarchy, a tree of boxes over a list of objects. Each
+/// branch has the box around all of the objects below it, a ray that misses
+/// the box of a branch can skip every object below it. Objects that go on
+/// forever, such as planes, can't be split into smaller boxes and are always
+/// returned.
+#[derive(Debug)]
+pub(crate) struct Bvh {
+    bounds: Vec<Bounds>,
+    nodes: Vec<Node>,
+    unbounded: Vec<usize>,
+}
+
+#[derive(Debug)]
+enum Node {
+    Leaf {
+        bounds: Bounds,
+        items: Vec<usize>,
+    },
+    Branch {
+        bounds: Bounds,
+        left: usize,
+        right: usize,
+    },
+}
+
+impl Bvh {
+    /// Build the hierarchy over the `bounds` of each object, the objects are
+    /// referred to by their index in `bounds`.
+    pub(crate) fn new(bounds: Vec<Bounds>) -> Bvh {
+        let (unbounded, bounded): (Vec<usize>, Vec<usize>) =
+            (0..bounds.len()).partition(|&i| bounds[i].is_infinite());
+
+        let mut bvh = Bvh {
+            bounds,
+            nodes: Vec::new(),
+            unbounded,
+        };
+        if !bounded.is_empty() {
+            bvh.build(bounded);
+        }
+        bvh
+    }
+
+    // Add the node for the `items` and the nodes below it, returning the index
+    // of the node. The items are split in half along the axis their centers
+    // are the most spread out along.
+    fn build(&mut self, mut items: Vec<usize>) -> usize {
+        let bounds = items
+            .iter()
+            .fold(Bounds::empty(), |b, &i| b.merge(self.bounds[i]));
+
+        if items.len() <= LEAF_SIZE {
+            self.nodes.push(Node::Leaf { bounds, items });
+            return self.nodes.len() - 1;
+        }
+
+        let center = |b: &Bounds| {
+            [
+                (b.min.x + b.max.x) / 2.0,
+                (b.min.y + b.max.y) / 2.0,
+                (b.min.z + b.max.z) / 2.0,
+            ]
+        };
+        let (mut low, mut high) = ([f64::INFINITY; 3], [f64::NEG_INFINITY; 3]);
+        for &i in &items {
+            let c = center(&self.bounds[i]);
+            for axis in 0..3 {
+                low[axis] = low[axis].min(c[axis]);
+                high[axis] = high[axis].max(c[axis]);
+            }
+        }
+        let axis = (0..3)
+            .max_by(|&a, &b| (high[a] - low[a]).total_cmp(&(high[b] - low[b])))
+            .unwrap();
+
+        items.sort_by(|&a, &b| {
+            center(&self.bounds[a])[axis].total_cmp(&center(&self.bounds[b])[axis])
+        });
+        let right_items = items.split_off(items.len() / 2);
+
+        // Reserve the spot for the branch so it comes before its children.
+        self.nodes.push(Node::Leaf {
+            bounds,
+            items: Vec::new(),
+        });
+        let index = self.nodes.len() - 1;
+        let left = self.build(items);
+        let right = self.build(right_items);
+        self.nodes[index] = Node::Branch {
+            bounds,
+            left,
+            right,
+        };
+
+        index
+    }
+
+    /// The index of each object whose box the ray `r` passes through, in
+    /// order from the smallest index.
+    pub(crate) fn candidates(&self, r: Ray) -> Vec<usize> {
+        let mut found = self.unbounded.clone();
+        let mut stack = if self.nodes.is_empty() {
+            Vec::new()
+        } else {
+            vec![0]
+        };
+
+        while let Some(index) = stack.pop() {
+            match &self.nodes[index] {
+                Node::Leaf { bounds, items } => {
+                    if bounds.intersects(r) {
+                        found.extend(items.iter().filter(|&&i| self.bounds[i].intersects(r)));
+                    }
+                }
+                Node::Branch {
+                    bounds,
+                    left,
+                    right,
+                } => {
+                    if bounds.intersects(r) {
+                        stack.push(*right);
+                        stack.push(*left);
+                    }
+                }
+            }
+        }
+
+        found.sort_unstable();
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Point, Vector};
+
+    fn unit_box_at(x: f64, y: f64, z: f64) -> Bounds {
+        Bounds::new(
+            Point::new(x - 0.5, y - 0.5, z - 0.5),
+            Point::new(x + 0.5, y + 0.5, z + 0.5),
+        )
+    }
+
+    #[test]
+    fn the_candidates_are_the_boxes_the_ray_passes_through() {
+        let bounds: Vec<Bounds> = (0..20)
+            .map(|i| unit_box_at(i as f64 * 2.0, 0.0, 0.0))
+            .collect();
+        let bvh = Bvh::new(bounds);
+
+        let r = Ray::new(Point::new(6.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(bvh.candidates(r), vec![3]);
+
+        let r = Ray::new(Point::new(-5.0, 0.0, 0.0), Vector::new(1.0, 0.0, 0.0));
+        assert_eq!(bvh.candidates(r), (0..20).collect::<Vec<_>>());
+
+        let r = Ray::new(Point::new(-5.0, 3.0, 0.0), Vector::new(1.0, 0.0, 0.0));
+        assert!(bvh.candidates(r).is_empty());
+    }
+
+    #[test]
+    fn boxes_that_go_on_forever_are_always_candidates() {
+        let bounds = vec![
+            unit_box_at(0.0, 0.0, 0.0),
+            Bounds::infinite(),
+            unit_box_at(5.0, 0.0, 0.0),
+        ];
+        let bvh = Bvh::new(bounds);
+        let r = Ray::new(Point::new(5.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert_eq!(bvh.candidates(r), vec![1, 2]);
+        assert!(Bvh::new(Vec::new())
+            .candidates(Ray::new(
+                Point::new(0.0, 0.0, 0.0),
+                Vector::new(0.0, 0.0, 1.0)
+            ))
+            .is_empty());
+    }
+}
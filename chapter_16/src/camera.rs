@@ -1,6 +1,13 @@
 #[allow(unused_imports)]
 use crate::Color;
-use crate::{Canvas, Matrix, Point, Ray, World, IDENTITY};
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use crate::{
+    Canvas, Colors, Intersection, Matrix, Point, Ray, Rng, Transformation, Vector, World, IDENTITY,
+};
 
 /// Encapsulates the view and provides an interface for rendering the world
 /// onto a [`Canvas`]. The [`Canvas`] is exactly one unit in front of the
@@ -12,6 +19,16 @@ pub struct Camera {
     pub vsize: usize,
     /// Camera transformation matrix.
     pub transform: Matrix,
+    /// Number of rays cast through each pixel, each jittered randomly within
+    /// the pixel and the colors averaged. Defaults to `1`, a single ray
+    /// through the center of the pixel.
+    pub samples: usize,
+    /// Random number generator used to jitter the rays. Each pixel is jittered
+    /// by its own generator started from this one and the position of the
+    /// pixel, rendering with the same seed produces the same image no matter
+    /// the order the pixels are rendered in.
+    pub rng: Rng,
+    field_of_view: f64,
     half_width: f64,
     half_height: f64,
     pixel_size: f64,
@@ -24,63 +41,497 @@ impl Camera {
     /// When the field of view is small, the view will be "zoomed in". Magnifying
     /// a smaller area of the scene.
     pub fn new(hsize: usize, vsize: usize, field_of_view: f64) -> Camera {
-        let half_view = (field_of_view / 2.0).tan();
-        let aspect = hsize as f64 / vsize as f64;
+        let mut camera = Camera {
+            hsize,
+            vsize,
+            transform: IDENTITY,
+            samples: 1,
+            rng: Rng::default(),
+            field_of_view,
+            half_width: 0.0,
+            half_height: 0.0,
+            pixel_size: 0.0,
+        };
+        camera.update_canvas();
+        camera
+    }
 
-        let mut half_width = half_view * aspect;
-        let mut half_height = half_view;
+    // Find the size of the canvas and its pixels from the size of the canvas
+    // in pixels and the field of view.
+    fn update_canvas(&mut self) {
+        let half_view = (self.field_of_view / 2.0).tan();
+        let aspect = self.hsize as f64 / self.vsize as f64;
 
         if aspect >= 1.0 {
-            half_width = half_view;
-            half_height = half_view / aspect;
+            self.half_width = half_view;
+            self.half_height = half_view / aspect;
+        } else {
+            self.half_width = half_view * aspect;
+            self.half_height = half_view;
         }
 
-        let pixel_size = (half_width * 2.0) / hsize as f64;
+        self.pixel_size = (self.half_width * 2.0) / self.hsize as f64;
+    }
 
-        Camera {
-            hsize,
-            vsize,
-            transform: IDENTITY,
-            half_width,
-            half_height,
-            pixel_size,
-        }
+    /// The angle that describes how much the camera can see.
+    pub fn field_of_view(&self) -> f64 {
+        self.field_of_view
+    }
+
+    /// Change the field of view of the camera, the size of the canvas and its
+    /// pixels are found again for the new angle.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{float_eq, Camera};
+    /// use std::f64::consts::PI;
+    ///
+    /// let mut c = Camera::new(200, 125, PI / 3.0);
+    /// c.set_field_of_view(PI / 2.0);
+    ///
+    /// assert_eq!(c.field_of_view(), PI / 2.0);
+    /// assert!(float_eq(c.pixel_size(), 0.01));
+    /// ```
+    pub fn set_field_of_view(&mut self, field_of_view: f64) {
+        self.field_of_view = field_of_view;
+        self.update_canvas();
+    }
+
+    /// Change the horizontal and vertical size of the canvas, the size of the
+    /// pixels are found again for the new size. Use this instead of setting
+    /// `hsize` and `vsize` directly.
+    pub fn set_size(&mut self, hsize: usize, vsize: usize) {
+        self.hsize = hsize;
+        self.vsize = vsize;
+        self.update_canvas();
+    }
+
+    /// Construct a `Camera` the same as `new`, positioned at `from` looking at
+    /// `to` with `up` being roughly the upward direction of the view.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Camera, Point, Vector};
+    /// use std::f64::consts::PI;
+    ///
+    /// let c = Camera::new_look_at(
+    ///     11,
+    ///     11,
+    ///     PI / 2.0,
+    ///     Point::new(0.0, 0.0, -5.0),
+    ///     Point::new(0.0, 0.0, 0.0),
+    ///     Vector::new(0.0, 1.0, 0.0),
+    /// );
+    /// let r = c.ray_for_pixel(5.0, 5.0);
+    ///
+    /// assert_eq!(r.origin, Point::new(0.0, 0.0, -5.0));
+    /// assert_eq!(r.direction, Vector::new(0.0, 0.0, 1.0));
+    /// ```
+    pub fn new_look_at(
+        hsize: usize,
+        vsize: usize,
+        field_of_view: f64,
+        from: Point,
+        to: Point,
+        up: Vector,
+    ) -> Camera {
+        let mut camera = Camera::new(hsize, vsize, field_of_view);
+        camera.look_at(from, to, up);
+        camera
+    }
+
+    /// Set the `transform` of the camera to the view from `from` looking at
+    /// `to`, with `up` being roughly the upward direction of the view.
+    pub fn look_at(&mut self, from: Point, to: Point, up: Vector) {
+        self.transform = Transformation::view_transform(from, to, up);
+    }
+
+    /// The size of a single pixel on the canvas in world units, the canvas is
+    /// one unit in front of the camera.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{float_eq, Camera};
+    /// use std::f64::consts::PI;
+    ///
+    /// let c = Camera::new(200, 125, PI / 2.0);
+    ///
+    /// assert!(float_eq(c.pixel_size(), 0.01));
+    /// ```
+    pub fn pixel_size(&self) -> f64 {
+        self.pixel_size
+    }
+
+    /// Half the width of the canvas in world units.
+    pub fn half_width(&self) -> f64 {
+        self.half_width
+    }
+
+    /// Half the height of the canvas in world units.
+    pub fn half_height(&self) -> f64 {
+        self.half_height
     }
 
     /// Returns a ray that starts at the camera and passes through the given
-    /// `x` and `y` pixel on the canvas.
-    pub fn ray_for_pixel(&mut self, px: f64, py: f64) -> Ray {
-        // the offset from the edge of the canvas to the pixel's center
-        let x_offset = (px + 0.5) * self.pixel_size;
-        let y_offset = (py + 0.5) * self.pixel_size;
-
-        // the untransformed coordinates of the pixel in world space.
-        // the camera looks toward -z, so +x is to the *left*.
-        let world_x = self.half_width - x_offset;
-        let world_y = self.half_height - y_offset;
-
-        // using the camera matrix, transform teh canvas point and the origin,
-        // and then compute the ray's direction vector.
-        // the canvas is at z: -1.
-        let pixel = self.transform.inverse() * Point::new(world_x, world_y, -1.0);
+    /// `x` and `y` pixel on the canvas. Whole numbers are the center of a
+    /// pixel, a fraction moves the ray across the pixel, which is each
+    /// `pixel_size` wide, for sampling more than one point of a pixel.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Camera, Point, Vector};
+    /// use std::f64::consts::PI;
+    ///
+    /// let c = Camera::new(201, 101, PI / 2.0);
+    ///
+    /// // through the center of the canvas
+    /// let r = c.ray_for_pixel(100.0, 50.0);
+    /// assert_eq!(r.origin, Point::new(0.0, 0.0, 0.0));
+    /// assert_eq!(r.direction, Vector::new(0.0, 0.0, -1.0));
+    ///
+    /// // through a corner of the canvas
+    /// let r = c.ray_for_pixel(0.0, 0.0);
+    /// assert_eq!(r.origin, Point::new(0.0, 0.0, 0.0));
+    /// assert_eq!(r.direction, Vector::new(0.66519, 0.33259, -0.66851));
+    /// ```
+    pub fn ray_for_pixel(&self, px: f64, py: f64) -> Ray {
         let origin = self.transform.inverse() * Point::new(0.0, 0.0, 0.0);
-        let direction = (pixel - origin).normalize();
+        let direction_for = |px: f64, py: f64| {
+            // the offset from the edge of the canvas to the pixel's center
+            let x_offset = (px + 0.5) * self.pixel_size;
+            let y_offset = (py + 0.5) * self.pixel_size;
+
+            // the untransformed coordinates of the pixel in world space.
+            // the camera looks toward -z, so +x is to the *left*.
+            let world_x = self.half_width - x_offset;
+            let world_y = self.half_height - y_offset;
+
+            // using the camera matrix, transform teh canvas point and the origin,
+            // and then compute the ray's direction vector.
+            // the canvas is at z: -1.
+            let pixel = self.transform.inverse() * Point::new(world_x, world_y, -1.0);
+            (pixel - origin).normalize()
+        };
+        let direction = direction_for(px, py);
 
-        Ray::new(origin, direction)
+        // the change in direction to the next pixel across and down, used to
+        // find how much of a surface the pixel covers.
+        Ray::new(origin, direction).with_differentials(
+            direction_for(px + 1.0, py) - direction,
+            direction_for(px, py + 1.0) - direction,
+        )
     }
 
     /// Uses the camera to render an image of the given world. The `render`
     /// function creates a ray for each pixel of the canvas using the
     /// `ray_for_pixel` function. The computed [`Ray`] is then projected
-    /// into the [`World`] using the `color_at` function of the [`World`] to get
-    /// a [`Color`] for an object intersected by the [`Ray`] if there is one.
+    /// into the [`World`] using the `color_at_default` function of the
+    /// [`World`] to get a [`Color`] for an object intersected by the [`Ray`] if
+    /// there is one.
     pub fn render(&mut self, world: &World) -> Canvas {
+        self.render_with_progress(world, |_, _| {})
+    }
+
+    /// Render the given world the same as `render`, calling `on_row` with the
+    /// number of rows done and the total number of rows after each row of the
+    /// canvas is completed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Camera, World};
+    /// use std::f64::consts::PI;
+    ///
+    /// let w = World::default();
+    /// let c = Camera::new(11, 11, PI / 2.0);
+    /// let mut rows = 0;
+    /// c.render_with_progress(&w, |done, total| {
+    ///     assert_eq!(total, 11);
+    ///     rows += 1;
+    ///     assert_eq!(done, rows);
+    /// });
+    ///
+    /// assert_eq!(rows, 11);
+    /// ```
+    pub fn render_with_progress(
+        &self,
+        world: &World,
+        mut on_row: impl FnMut(usize, usize),
+    ) -> Canvas {
+        let mut canvas = Canvas::new(self.hsize, self.vsize);
+
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                let color = self.color_for_pixel(world, x, y);
+                canvas.write_pixel(x, y, color);
+            }
+
+            on_row(y + 1, self.vsize);
+        }
+
+        canvas
+    }
+
+    /// Render the given world the same as `render` along with an alpha channel
+    /// for compositing the image over another. The alpha of each pixel is
+    /// `true` when the ray through the center of the pixel hits an object and
+    /// `false` when it misses every object, stored a row at a time the same
+    /// as the pixels of the [`Canvas`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Camera, World};
+    /// use std::f64::consts::PI;
+    ///
+    /// let w = World::default();
+    /// let c = Camera::new(11, 11, PI / 2.0);
+    /// let (image, alpha) = c.render_with_alpha(&w);
+    ///
+    /// assert_eq!(alpha.len(), image.width() * image.height());
+    /// ```
+    pub fn render_with_alpha(&self, world: &World) -> (Canvas, Vec<bool>) {
         let mut canvas = Canvas::new(self.hsize, self.vsize);
+        let mut alpha = Vec::with_capacity(self.hsize * self.vsize);
 
         for y in 0..self.vsize {
             for x in 0..self.hsize {
+                let color = self.color_for_pixel(world, x, y);
+                canvas.write_pixel(x, y, color);
+
                 let ray = self.ray_for_pixel(x as f64, y as f64);
-                let color = world.color_at(ray, 5);
+                let hit = match world.intersect_world(ray) {
+                    Some(xs) => Intersection::hit(&xs).is_some(),
+                    None => false,
+                };
+                alpha.push(hit);
+            }
+        }
+
+        (canvas, alpha)
+    }
+
+    /// Render the given world the same as `render` one `tile_size` by
+    /// `tile_size` block of pixels at a time. Each block is rendered to its own
+    /// [`Canvas`] then copied into the final [`Canvas`]. The blocks along the
+    /// right and bottom edges are smaller when the size of the canvas is not a
+    /// multiple of `tile_size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tile_size` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Camera, World};
+    /// use std::f64::consts::PI;
+    ///
+    /// let w = World::default();
+    /// let mut c = Camera::new(11, 11, PI / 2.0);
+    /// let image = c.render_tiles(&w, 4);
+    ///
+    /// assert_eq!(image.pixel_at(5, 5), c.render(&w).pixel_at(5, 5));
+    /// ```
+    pub fn render_tiles(&self, world: &World, tile_size: usize) -> Canvas {
+        assert!(tile_size > 0, "tile size must be greater than 0");
+        let mut canvas = Canvas::new(self.hsize, self.vsize);
+
+        for y in (0..self.vsize).step_by(tile_size) {
+            for x in (0..self.hsize).step_by(tile_size) {
+                let w = tile_size.min(self.hsize - x);
+                let h = tile_size.min(self.vsize - y);
+                let tile = self.render_tile(world, x, y, w, h);
+
+                canvas.blit(&tile, x, y);
+            }
+        }
+
+        canvas
+    }
+
+    /// Render the given world the same as `render` using `threads` threads.
+    /// Each thread takes the next row not yet rendered until every row is
+    /// done, so a thread finishing rows of empty sky takes on more rows
+    /// instead of waiting on a thread stuck with the rows full of objects.
+    /// The image is the same as `render`, even with more than one sample per
+    /// pixel, as the jitter of each pixel does not depend on which thread
+    /// renders it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threads` is `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Camera, World};
+    /// use std::f64::consts::PI;
+    ///
+    /// let w = World::default();
+    /// let mut c = Camera::new(11, 11, PI / 2.0);
+    /// let image = c.render_balanced(&w, 4);
+    ///
+    /// assert_eq!(image.pixel_at(5, 5), c.render(&w).pixel_at(5, 5));
+    /// ```
+    pub fn render_balanced(&self, world: &World, threads: usize) -> Canvas {
+        assert!(threads > 0, "at least one thread is needed to render");
+        let next_row = AtomicUsize::new(0);
+
+        let rows: Vec<(usize, Canvas)> = thread::scope(|s| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    s.spawn(|| {
+                        let mut rows = Vec::new();
+                        loop {
+                            let y = next_row.fetch_add(1, Ordering::Relaxed);
+                            if y >= self.vsize {
+                                break rows;
+                            }
+
+                            rows.push((y, self.render_tile(world, 0, y, self.hsize, 1)));
+                        }
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .flat_map(|w| w.join().expect("a render thread panicked"))
+                .collect()
+        });
+
+        let mut canvas = Canvas::new(self.hsize, self.vsize);
+        for (y, row) in rows {
+            canvas.blit(&row, 0, y);
+        }
+
+        canvas
+    }
+
+    /// Render only the pixels from `x0` up to but not including `x1` across and
+    /// from `y0` up to but not including `y1` down, such as when tuning one
+    /// corner of a scene. The returned [`Canvas`] is the size of the region,
+    /// the pixel at `x0` and `y0` is at `0` and `0` on the canvas.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the region is not inside of the camera's canvas.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Camera, World};
+    /// use std::f64::consts::PI;
+    ///
+    /// let w = World::default();
+    /// let mut c = Camera::new(11, 11, PI / 2.0);
+    /// let region = c.render_region(&w, 4, 5, 7, 6);
+    ///
+    /// assert_eq!(region.width(), 3);
+    /// assert_eq!(region.height(), 1);
+    /// assert_eq!(region.pixel_at(1, 0), c.render(&w).pixel_at(5, 5));
+    /// ```
+    pub fn render_region(
+        &self,
+        world: &World,
+        x0: usize,
+        y0: usize,
+        x1: usize,
+        y1: usize,
+    ) -> Canvas {
+        assert!(
+            x0 <= x1 && y0 <= y1 && x1 <= self.hsize && y1 <= self.vsize,
+            "region must be inside of the canvas"
+        );
+
+        self.render_tile(world, x0, y0, x1 - x0, y1 - y0)
+    }
+
+    /// Render `frames` images of the given world, such as a turntable
+    /// animation. Before each frame the camera transform is replaced with the
+    /// matrix returned by `orbit` for the frame number, counting from `0`.
+    /// The camera itself is not changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Camera, Point, Transformation, Vector, World};
+    /// use std::f64::consts::PI;
+    ///
+    /// let w = World::default();
+    /// let c = Camera::new(11, 11, PI / 2.0);
+    /// let frames = c.render_sequence(&w, 4, |frame| {
+    ///     let angle = frame as f64 * PI / 2.0;
+    ///     Transformation::view_transform(
+    ///         Point::new(5.0 * angle.sin(), 0.0, -5.0 * angle.cos()),
+    ///         Point::new(0.0, 0.0, 0.0),
+    ///         Vector::new(0.0, 1.0, 0.0),
+    ///     )
+    /// });
+    ///
+    /// assert_eq!(frames.len(), 4);
+    /// ```
+    pub fn render_sequence(
+        &self,
+        world: &World,
+        frames: usize,
+        orbit: impl Fn(usize) -> Matrix,
+    ) -> Vec<Canvas> {
+        (0..frames)
+            .map(|frame| {
+                let camera = Camera {
+                    transform: orbit(frame),
+                    ..*self
+                };
+                camera.render_with_progress(world, |_, _| {})
+            })
+            .collect()
+    }
+
+    /// Render the surface normals of the given world instead of shading it,
+    /// useful for debugging the geometry of a scene. For each pixel the
+    /// world space normal at the hit is mapped from `-1..1` to a [`Color`]
+    /// with each channel `0..1`, `x` to red, `y` to green and `z` to blue. A
+    /// pixel where the ray misses every object is black.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Camera, Color, Point, Transformation, Vector, World};
+    /// use std::f64::consts::PI;
+    ///
+    /// let w = World::default();
+    /// let mut c = Camera::new(11, 11, PI / 2.0);
+    /// c.transform = Transformation::view_transform(
+    ///     Point::new(0.0, 5.0, 0.0),
+    ///     Point::new(0.0, 0.0, 0.0),
+    ///     Vector::new(0.0, 0.0, 1.0),
+    /// );
+    /// let image = c.render_normals(&w);
+    ///
+    /// assert_eq!(image.pixel_at(5, 5), Color::new(0.5, 1.0, 0.5));
+    /// ```
+    pub fn render_normals(&self, world: &World) -> Canvas {
+        let mut canvas = Canvas::new(self.hsize, self.vsize);
+
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                let ray = self.ray_for_pixel(x as f64, y as f64);
+                let color = match world.intersect_world(ray) {
+                    Some(xs) => match Intersection::hit(&xs) {
+                        Some(i) => {
+                            let n = i.prepare_computations(ray, &xs, Some(world)).normalv;
+                            Color::new((n.x + 1.0) / 2.0, (n.y + 1.0) / 2.0, (n.z + 1.0) / 2.0)
+                        }
+                        None => Colors::BLACK,
+                    },
+                    None => Colors::BLACK,
+                };
 
                 canvas.write_pixel(x, y, color);
             }
@@ -88,13 +539,96 @@ impl Camera {
 
         canvas
     }
+
+    /// Render a grayscale depth pass of the given world without shading it.
+    /// Each pixel is `1 / (1 + t)` in all three channels where `t` is the
+    /// distance to the hit, so nearer objects are brighter. A pixel where the
+    /// ray misses every object is black.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Camera, Color, Point, Transformation, Vector, World};
+    /// use std::f64::consts::PI;
+    ///
+    /// let w = World::default();
+    /// let mut c = Camera::new(11, 11, PI / 2.0);
+    /// c.transform = Transformation::view_transform(
+    ///     Point::new(0.0, 0.0, -5.0),
+    ///     Point::new(0.0, 0.0, 0.0),
+    ///     Vector::new(0.0, 1.0, 0.0),
+    /// );
+    /// let image = c.render_depth(&w);
+    ///
+    /// assert_eq!(image.pixel_at(5, 5), Color::new(0.2, 0.2, 0.2));
+    /// ```
+    pub fn render_depth(&self, world: &World) -> Canvas {
+        let mut canvas = Canvas::new(self.hsize, self.vsize);
+
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                let ray = self.ray_for_pixel(x as f64, y as f64);
+                let color = match world.intersect_world(ray) {
+                    Some(xs) => match Intersection::hit(&xs) {
+                        Some(i) => {
+                            let depth = 1.0 / (1.0 + i.t);
+                            Color::new(depth, depth, depth)
+                        }
+                        None => Colors::BLACK,
+                    },
+                    None => Colors::BLACK,
+                };
+
+                canvas.write_pixel(x, y, color);
+            }
+        }
+
+        canvas
+    }
+
+    // Render the block of pixels `w` wide and `h` high with the top left
+    // corner at `x` and `y`.
+    fn render_tile(&self, world: &World, x: usize, y: usize, w: usize, h: usize) -> Canvas {
+        let mut tile = Canvas::new(w, h);
+
+        for ty in 0..h {
+            for tx in 0..w {
+                let color = self.color_for_pixel(world, x + tx, y + ty);
+                tile.write_pixel(tx, ty, color);
+            }
+        }
+
+        tile
+    }
+
+    // Color of the pixel at `x` and `y`, the average of `samples` rays
+    // jittered within the pixel. The jitter comes from a generator started
+    // from `rng` and the pixel, so the pixel is the same however the image is
+    // rendered.
+    fn color_for_pixel(&self, world: &World, x: usize, y: usize) -> Color {
+        if self.samples <= 1 {
+            return world.color_at_default(self.ray_for_pixel(x as f64, y as f64));
+        }
+
+        let mut rng = self.rng;
+        let mut rng = Rng::new(rng.next_u64().wrapping_add((y * self.hsize + x) as u64));
+
+        let mut color = Colors::BLACK;
+        for _ in 0..self.samples {
+            let px = x as f64 + rng.next_f64() - 0.5;
+            let py = y as f64 + rng.next_f64() - 0.5;
+            color = color + world.color_at_default(self.ray_for_pixel(px, py));
+        }
+
+        color * (1.0 / self.samples as f64)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::f64::consts::PI;
 
-    use crate::{float_eq, Color, Point, Transformation, Vector, World};
+    use crate::{float_eq, shapes::Sphere, Color, Point, Transformation, Vector, World};
 
     use super::*;
 
@@ -130,11 +664,58 @@ mod tests {
         assert!(float_eq(c.pixel_size, 0.01));
     }
 
+    #[test]
+    fn the_canvas_dimensions_are_readable() {
+        let c = Camera::new(200, 125, PI / 2.0);
+
+        assert!(float_eq(c.pixel_size(), 0.01));
+        assert!(float_eq(c.half_width(), 1.0));
+        assert!(float_eq(c.half_height(), 0.625));
+    }
+
+    #[test]
+    fn changing_the_field_of_view_matches_a_new_camera() {
+        let mut c = Camera::new(200, 125, PI / 2.0);
+        c.set_field_of_view(PI / 3.0);
+        let fresh = Camera::new(200, 125, PI / 3.0);
+
+        assert!(float_eq(c.pixel_size(), fresh.pixel_size()));
+        assert!(float_eq(c.half_width(), fresh.half_width()));
+        assert!(float_eq(c.half_height(), fresh.half_height()));
+    }
+
+    #[test]
+    fn changing_the_size_matches_a_new_camera() {
+        let mut c = Camera::new(200, 125, PI / 2.0);
+        c.set_size(125, 200);
+        let fresh = Camera::new(125, 200, PI / 2.0);
+
+        assert_eq!((c.hsize, c.vsize), (125, 200));
+        assert!(float_eq(c.pixel_size(), fresh.pixel_size()));
+        assert!(float_eq(c.half_width(), fresh.half_width()));
+        assert!(float_eq(c.half_height(), fresh.half_height()));
+    }
+
+    #[test]
+    fn look_at_sets_the_view_transform() {
+        let from = Point::new(1.0, 3.0, 2.0);
+        let to = Point::new(4.0, -2.0, 8.0);
+        let up = Vector::new(1.0, 1.0, 0.0);
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        c.look_at(from, to, up);
+
+        assert_eq!(c.transform, Transformation::view_transform(from, to, up));
+        assert_eq!(
+            Camera::new_look_at(11, 11, PI / 2.0, from, to, up).transform,
+            c.transform
+        );
+    }
+
     // Chapter 7 Making a Scene
     // Page 103
     #[test]
     fn constructing_a_ray_through_the_center_of_canvas() {
-        let mut c = Camera::new(201, 101, PI / 2.0);
+        let c = Camera::new(201, 101, PI / 2.0);
         let r = c.ray_for_pixel(100.0, 50.0);
 
         assert_eq!(r.origin, Point::new(0.0, 0.0, 0.0));
@@ -145,7 +726,7 @@ mod tests {
     // Page 103
     #[test]
     fn constructing_a_ray_through_a_corner_of_the_canvas() {
-        let mut c = Camera::new(201, 101, PI / 2.0);
+        let c = Camera::new(201, 101, PI / 2.0);
         let r = c.ray_for_pixel(0.0, 0.0);
 
         assert_eq!(r.origin, Point::new(0.0, 0.0, 0.0));
@@ -184,4 +765,241 @@ mod tests {
 
         assert_eq!(image.pixel_at(5, 5), Color::new(0.38066, 0.47583, 0.2855));
     }
+
+    #[test]
+    fn rendering_a_world_reports_progress_for_each_row() {
+        let w = World::default();
+        let mut c = Camera::new(11, 7, PI / 2.0);
+        let from = Point::new(0.0, 0.0, -5.0);
+        let to = Point::new(0.0, 0.0, 0.0);
+        let up = Vector::new(0.0, 1.0, 0.0);
+        c.transform = Transformation::view_transform(from, to, up);
+        let mut progress = Vec::new();
+        let image = c.render_with_progress(&w, |done, total| progress.push((done, total)));
+
+        assert_eq!(progress, (1..=7).map(|y| (y, 7)).collect::<Vec<_>>());
+        assert_eq!(image.pixel_at(5, 3), c.render(&w).pixel_at(5, 3));
+    }
+
+    #[test]
+    fn rendering_a_sequence_applies_each_frame_transform() {
+        let w = World::default();
+        let c = Camera::new(11, 11, PI / 2.0);
+        let frames = c.render_sequence(&w, 2, |frame| {
+            let from = if frame == 0 {
+                Point::new(0.0, 0.0, -5.0)
+            } else {
+                Point::new(0.0, 0.0, 5.0)
+            };
+            Transformation::view_transform(
+                from,
+                Point::new(0.0, 0.0, 0.0),
+                Vector::new(0.0, 1.0, 0.0),
+            )
+        });
+
+        assert_eq!(frames.len(), 2);
+        assert_ne!(frames[0].pixel_at(5, 5), frames[1].pixel_at(5, 5));
+        assert_eq!(c.transform, IDENTITY);
+    }
+
+    #[test]
+    fn rendering_a_world_in_tiles_matches_rendering_by_rows() {
+        let w = World::default();
+        let mut c = Camera::new(13, 9, PI / 2.0);
+        let from = Point::new(0.0, 0.0, -5.0);
+        let to = Point::new(0.0, 0.0, 0.0);
+        let up = Vector::new(0.0, 1.0, 0.0);
+        c.transform = Transformation::view_transform(from, to, up);
+        let image = c.render(&w);
+
+        for tile_size in [1, 4, 5, 20] {
+            let tiled = c.render_tiles(&w, tile_size);
+            for y in 0..9 {
+                for x in 0..13 {
+                    let (a, b) = (tiled.pixel_at(x, y), image.pixel_at(x, y));
+                    assert_eq!((a.red, a.green, a.blue), (b.red, b.green, b.blue));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rendering_balanced_rows_matches_rendering_by_rows() {
+        let mut w = World::default();
+        // a pile of small spheres in the top rows of the image only
+        for i in 0..40 {
+            let mut s = Sphere::new();
+            s.transform = Transformation::new()
+                .scale(0.1, 0.1, 0.1)
+                .translate(i as f64 * 0.1 - 2.0, 1.5 + (i % 3) as f64 * 0.1, 0.0)
+                .build();
+            s.material.reflective = 0.5;
+            w.add_object(Box::new(s));
+        }
+        let mut c = Camera::new(21, 17, PI / 2.0);
+        c.transform = Transformation::view_transform(
+            Point::new(0.0, 0.0, -5.0),
+            Point::new(0.0, 0.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+        );
+        let rows = c.render(&w);
+
+        for threads in [1, 3, 8] {
+            let balanced = c.render_balanced(&w, threads);
+            for y in 0..c.vsize {
+                for x in 0..c.hsize {
+                    assert_eq!(balanced.pixel_at(x, y), rows.pixel_at(x, y));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rendering_a_region_matches_the_crop_of_a_full_render() {
+        let w = World::default();
+        let mut c = Camera::new(13, 9, PI / 2.0);
+        let from = Point::new(0.0, 0.0, -5.0);
+        let to = Point::new(0.0, 0.0, 0.0);
+        let up = Vector::new(0.0, 1.0, 0.0);
+        c.transform = Transformation::view_transform(from, to, up);
+        let crop = c.render(&w).sub_region(3, 2, 6, 5);
+        let region = c.render_region(&w, 3, 2, 9, 7);
+
+        assert_eq!((region.width(), region.height()), (6, 5));
+        for y in 0..5 {
+            for x in 0..6 {
+                let (a, b) = (region.pixel_at(x, y), crop.pixel_at(x, y));
+                assert_eq!((a.red, a.green, a.blue), (b.red, b.green, b.blue));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn rendering_a_region_outside_of_the_canvas_panics() {
+        let c = Camera::new(11, 11, PI / 2.0);
+        c.render_region(&World::default(), 5, 5, 12, 8);
+    }
+
+    #[test]
+    fn rendering_the_normals_of_a_world() {
+        let w = World::default();
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        let from = Point::new(0.0, 0.0, 5.0);
+        let to = Point::new(0.0, 0.0, 0.0);
+        let up = Vector::new(0.0, 1.0, 0.0);
+        c.transform = Transformation::view_transform(from, to, up);
+        let image = c.render_normals(&w);
+
+        assert_eq!(image.pixel_at(5, 5), Color::new(0.5, 0.5, 1.0));
+        assert_eq!(image.pixel_at(0, 0), Color::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn a_closer_object_is_brighter_in_the_depth_render() {
+        let c = Camera::new(11, 11, PI / 2.0);
+        let depth_at = |z: f64| {
+            let mut w = World::new();
+            let mut s = Sphere::new();
+            s.transform = Transformation::new().translate(0.0, 0.0, z).build();
+            w.add_object(Box::new(s));
+            c.render_depth(&w).pixel_at(5, 5)
+        };
+        let near = depth_at(-3.0);
+        let far = depth_at(-6.0);
+
+        assert_eq!(near, Color::new(1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0));
+        assert!(near.red > far.red);
+        assert_eq!(
+            c.render_depth(&World::new()).pixel_at(5, 5),
+            Color::new(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn rendering_with_the_same_seed_produces_the_same_image() {
+        let w = World::default();
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        let from = Point::new(0.0, 0.0, -5.0);
+        let to = Point::new(0.0, 0.0, 0.0);
+        let up = Vector::new(0.0, 1.0, 0.0);
+        c.transform = Transformation::view_transform(from, to, up);
+        c.samples = 4;
+        c.rng = Rng::new(1);
+        let first = c.render(&w);
+        let second = c.render(&w);
+        c.rng.seed(2);
+        let third = c.render(&w);
+
+        let pixels: Vec<(usize, usize)> =
+            (0..11).flat_map(|y| (0..11).map(move |x| (x, y))).collect();
+
+        assert!(pixels
+            .iter()
+            .all(|&(x, y)| first.pixel_at(x, y) == second.pixel_at(x, y)));
+        assert!(pixels
+            .iter()
+            .any(|&(x, y)| first.pixel_at(x, y) != third.pixel_at(x, y)));
+    }
+
+    // a camera looking at the default world taking several jittered samples
+    // of each pixel
+    fn jittered_camera() -> Camera {
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        c.transform = Transformation::view_transform(
+            Point::new(0.0, 0.0, -5.0),
+            Point::new(0.0, 0.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+        );
+        c.samples = 4;
+        c.rng = Rng::new(3);
+        c
+    }
+
+    #[test]
+    fn rendering_in_tiles_with_jitter_matches_render() {
+        let w = World::default();
+        let mut c = jittered_camera();
+        let image = c.render(&w);
+
+        image.assert_close(&c.render_tiles(&w, 4), 0.0);
+        image.assert_close(&c.render_tiles(&w, 3), 0.0);
+    }
+
+    #[test]
+    fn rendering_a_region_with_jitter_matches_render() {
+        let w = World::default();
+        let mut c = jittered_camera();
+        let image = c.render(&w);
+
+        image
+            .sub_region(2, 3, 6, 5)
+            .assert_close(&c.render_region(&w, 2, 3, 8, 8), 0.0);
+    }
+
+    #[test]
+    fn rendering_balanced_with_jitter_matches_render() {
+        let w = World::default();
+        let mut c = jittered_camera();
+        let image = c.render(&w);
+
+        image.assert_close(&c.render_balanced(&w, 3), 0.0);
+    }
+
+    #[test]
+    fn the_alpha_of_a_render_is_set_only_where_a_ray_hits() {
+        let w = World::default();
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        c.transform = Transformation::view_transform(
+            Point::new(0.0, 0.0, -5.0),
+            Point::new(0.0, 0.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+        );
+        let (image, alpha) = c.render_with_alpha(&w);
+
+        assert!(alpha[5 + 5 * 11]);
+        assert!(!alpha[0]);
+        image.assert_close(&c.render(&w), 0.0);
+    }
 }
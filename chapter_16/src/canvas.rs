@@ -6,6 +6,7 @@ const MAXIMUM_PPM_LINE_LENGTH: usize = 70;
 ///
 /// The pixels are stored in a linear 1D array indexing a pixel is done with
 /// this formula `index = x + y * width`.
+#[derive(Debug, Clone)]
 pub struct Canvas {
     width: usize,
     height: usize,
@@ -37,6 +38,16 @@ impl Canvas {
         }
     }
 
+    /// The number of pixels across the canvas.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of pixels down the canvas.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
     /// Output the canvas buffer to a string buffer in the PPM file format.
     ///
     /// Example
@@ -62,7 +73,46 @@ impl Canvas {
     /// assert_eq!("0 0 0 0 0 0 0 0 0 0 0 0 0 0 255", split[5]);
     /// ```
     pub fn canvas_to_ppm(&self) -> String {
-        let mut buffer = ["P3", &format!("{} {}", self.width, self.height), "255"].join("\n");
+        self.canvas_to_ppm_bits(8)
+    }
+
+    /// Output the canvas buffer to a string buffer in the PPM file format with
+    /// `bits` for each sample, either `8` for a maximum value of `255` or `16`
+    /// for a maximum value of `65535`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is not `8` or `16`.
+    ///
+    /// Example
+    ///
+    /// ```
+    /// use rustic_ray::{Canvas, Color};
+    ///
+    /// let mut c = Canvas::new(1, 1);
+    /// c.write_pixel(0, 0, Color::new(1.0, 0.5, 0.0));
+    /// let actual = c.canvas_to_ppm_bits(16);
+    /// let split = actual.split("\n").collect::<Vec<_>>();
+    ///
+    /// assert_eq!("65535", split[2]);
+    /// assert_eq!("65535 32768 0", split[3]);
+    /// ```
+    pub fn canvas_to_ppm_bits(&self, bits: u8) -> String {
+        assert!(
+            bits == 8 || bits == 16,
+            "PPM samples must be 8 or 16 bits, not {}",
+            bits
+        );
+        let levels = (1u32 << bits) as f64;
+        let max_value = (1u32 << bits) - 1;
+        let sample = |v: f64| format!("{}", ((v.clamp(0.0, 1.0) * levels) as u32).min(max_value));
+
+        let mut buffer = [
+            "P3",
+            &format!("{} {}", self.width, self.height),
+            &format!("{}", max_value),
+        ]
+        .join("\n");
         buffer.push('\n');
 
         let mut col_counter = 0;
@@ -70,7 +120,7 @@ impl Canvas {
             for x in 0..self.width {
                 let pixel = self.pixel_at(x, y);
 
-                for c in pixel.rgb_string_array().iter() {
+                for c in [sample(pixel.red), sample(pixel.green), sample(pixel.blue)].iter() {
                     if col_counter + c.len() + 1 > MAXIMUM_PPM_LINE_LENGTH {
                         buffer += "\n";
                         col_counter = 0;
@@ -107,6 +157,34 @@ impl Canvas {
         self.pixels[i]
     }
 
+    /// Returns the [`Color`] of the pixel nearest to the texture coordinate `u`
+    /// and `v`, each from `0.0` to `1.0`. `u` runs from the left to the right
+    /// of the canvas and `v` from the bottom to the top. Coordinates outside
+    /// of `0.0..=1.0` are clamped to the edge of the canvas and an empty canvas
+    /// is black.
+    ///
+    /// Example
+    /// ```
+    /// use rustic_ray::{Canvas, Color};
+    ///
+    /// let mut c = Canvas::new(10, 20);
+    /// c.write_pixel(9, 0, Color::new(1.0, 0.0, 0.0));
+    ///
+    /// assert_eq!(c.pixel_at_uv(1.0, 1.0), Color::new(1.0, 0.0, 0.0));
+    /// ```
+    pub fn pixel_at_uv(&self, u: f64, v: f64) -> Color {
+        if self.width == 0 || self.height == 0 {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+
+        let x = u.clamp(0.0, 1.0) * (self.width - 1) as f64;
+        let y = (1.0 - v.clamp(0.0, 1.0)) * (self.height - 1) as f64;
+        let x = (x.round() as usize).min(self.width - 1);
+        let y = (y.round() as usize).min(self.height - 1);
+
+        self.pixel_at(x, y)
+    }
+
     /// Write a pixel to the canvas at the specified `x` and `y` coordinates
     /// having the specified [`Color`].
     ///
@@ -124,6 +202,235 @@ impl Canvas {
         let i = x + y * self.width;
         self.pixels[i] = c;
     }
+
+    /// Returns a new canvas with each channel `c` of every pixel mapped to
+    /// `c / (1 + c)` using the Reinhard tone mapping operator. Bright colors
+    /// above `1.0` are compressed into `0.0..1.0` keeping their relative
+    /// brightness instead of being clamped by `canvas_to_ppm`. Negative
+    /// channels are treated as `0.0`.
+    ///
+    /// Example
+    /// ```
+    /// use rustic_ray::{Canvas, Color};
+    ///
+    /// let mut c = Canvas::new(10, 20);
+    /// c.write_pixel(2, 3, Color::new(1.0, 3.0, 0.0));
+    /// let mapped = c.tone_map_reinhard();
+    ///
+    /// assert_eq!(mapped.pixel_at(2, 3), Color::new(0.5, 0.75, 0.0));
+    /// ```
+    pub fn tone_map_reinhard(&self) -> Canvas {
+        let map = |c: f64| {
+            let c = c.max(0.0);
+            c / (1.0 + c)
+        };
+
+        Canvas {
+            width: self.width,
+            height: self.height,
+            pixels: self
+                .pixels
+                .iter()
+                .map(|p| Color::new(map(p.red), map(p.green), map(p.blue)))
+                .collect(),
+        }
+    }
+
+    /// Iterate over every pixel of the canvas with its coordinates as
+    /// `(x, y, color)`, row by row starting at the top left corner.
+    ///
+    /// Example
+    /// ```
+    /// use rustic_ray::{Canvas, Color};
+    ///
+    /// let mut c = Canvas::new(3, 2);
+    /// c.write_pixel(2, 1, Color::new(1.0, 0.0, 0.0));
+    /// let red: Vec<_> = c.iter_pixels().filter(|(_, _, p)| p.red > 0.0).collect();
+    ///
+    /// assert_eq!(red.len(), 1);
+    /// assert_eq!((red[0].0, red[0].1), (2, 1));
+    /// ```
+    pub fn iter_pixels(&self) -> impl Iterator<Item = (usize, usize, Color)> + '_ {
+        self.pixels
+            .iter()
+            .enumerate()
+            .map(move |(i, p)| (i % self.width, i / self.width, *p))
+    }
+
+    /// Returns a new canvas of the same size with every pixel replaced by the
+    /// color returned from `f` given the `x` and `y` of the pixel and its
+    /// color.
+    ///
+    /// Example
+    /// ```
+    /// use rustic_ray::{Canvas, Color};
+    ///
+    /// let c = Canvas::new(4, 4);
+    /// let gradient = c.map_pixels(|x, _, _| Color::new(x as f64 / 4.0, 0.0, 0.0));
+    ///
+    /// assert_eq!(gradient.pixel_at(2, 3), Color::new(0.5, 0.0, 0.0));
+    /// ```
+    pub fn map_pixels(&self, f: impl Fn(usize, usize, Color) -> Color) -> Canvas {
+        Canvas {
+            width: self.width,
+            height: self.height,
+            pixels: self.iter_pixels().map(|(x, y, p)| f(x, y, p)).collect(),
+        }
+    }
+
+    /// Returns a new canvas with each pixel the average of the pixels within
+    /// `radius` of it, a box blur. The blur is done as a horizontal pass and
+    /// then a vertical pass, at the edges only the pixels inside the canvas
+    /// are averaged. A `radius` of `0` copies the canvas.
+    ///
+    /// Example
+    /// ```
+    /// use rustic_ray::{Canvas, Color};
+    ///
+    /// let mut c = Canvas::new(5, 5);
+    /// c.write_pixel(2, 2, Color::new(9.0, 0.0, 0.0));
+    /// let blurred = c.blur(1);
+    ///
+    /// assert_eq!(blurred.pixel_at(1, 3), Color::new(1.0, 0.0, 0.0));
+    /// assert_eq!(blurred.pixel_at(0, 0), Color::new(0.0, 0.0, 0.0));
+    /// ```
+    pub fn blur(&self, radius: usize) -> Canvas {
+        self.blur_pass(radius, 1, 0).blur_pass(radius, 0, 1)
+    }
+
+    // Average each pixel with the pixels within `radius` along the direction
+    // `dx`, `dy`.
+    fn blur_pass(&self, radius: usize, dx: usize, dy: usize) -> Canvas {
+        self.map_pixels(|x, y, _| {
+            let (pos, len) = if dx > 0 {
+                (x, self.width)
+            } else {
+                (y, self.height)
+            };
+            let start = pos.saturating_sub(radius);
+            let end = (pos + radius).min(len - 1);
+
+            let mut sum = Color::new(0.0, 0.0, 0.0);
+            for i in start..=end {
+                sum = sum + self.pixel_at(x * dy + i * dx, y * dx + i * dy);
+            }
+            sum * (1.0 / (end - start + 1) as f64)
+        })
+    }
+
+    /// Copy the rectangle `w` pixels wide and `h` pixels high with the top left
+    /// corner at `x` and `y` to a new canvas. Any part of the rectangle outside
+    /// of the canvas is black.
+    ///
+    /// Example
+    /// ```
+    /// use rustic_ray::{Canvas, Color};
+    ///
+    /// let mut c = Canvas::new(10, 20);
+    /// c.write_pixel(2, 3, Color::new(1.0, 0.0, 0.0));
+    /// let region = c.sub_region(1, 2, 4, 4);
+    ///
+    /// assert_eq!(region.pixel_at(1, 1), Color::new(1.0, 0.0, 0.0));
+    /// ```
+    pub fn sub_region(&self, x: usize, y: usize, w: usize, h: usize) -> Canvas {
+        let mut region = Canvas::new(w, h);
+
+        for ry in 0..h.min(self.height.saturating_sub(y)) {
+            for rx in 0..w.min(self.width.saturating_sub(x)) {
+                region.write_pixel(rx, ry, self.pixel_at(x + rx, y + ry));
+            }
+        }
+
+        region
+    }
+
+    /// Paste the `src` canvas onto this canvas with the top left corner of
+    /// `src` at `dst_x` and `dst_y`. Any part of `src` that falls outside of
+    /// this canvas is clipped.
+    ///
+    /// Example
+    /// ```
+    /// use rustic_ray::{Canvas, Color};
+    ///
+    /// let mut tile = Canvas::new(2, 2);
+    /// tile.write_pixel(1, 1, Color::new(1.0, 0.0, 0.0));
+    /// let mut c = Canvas::new(10, 20);
+    /// c.blit(&tile, 4, 5);
+    ///
+    /// assert_eq!(c.pixel_at(5, 6), Color::new(1.0, 0.0, 0.0));
+    /// ```
+    pub fn blit(&mut self, src: &Canvas, dst_x: usize, dst_y: usize) {
+        for y in 0..src.height.min(self.height.saturating_sub(dst_y)) {
+            for x in 0..src.width.min(self.width.saturating_sub(dst_x)) {
+                self.write_pixel(dst_x + x, dst_y + y, src.pixel_at(x, y));
+            }
+        }
+    }
+
+    /// Returns the largest difference of any one channel of any one pixel
+    /// between this canvas and `other`. A channel that is not a number in
+    /// either canvas is an infinite difference, so a broken render is never
+    /// close to another. Panics if the canvases are not the same size.
+    ///
+    /// Example
+    /// ```
+    /// use rustic_ray::{Canvas, Color};
+    ///
+    /// let a = Canvas::new(10, 20);
+    /// let mut b = Canvas::new(10, 20);
+    /// b.write_pixel(2, 3, Color::new(0.0, 0.25, 0.0));
+    ///
+    /// assert_eq!(a.max_channel_diff(&b), 0.25);
+    /// ```
+    pub fn max_channel_diff(&self, other: &Canvas) -> f64 {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "canvases are different sizes, {}x{} and {}x{}",
+            self.width,
+            self.height,
+            other.width,
+            other.height
+        );
+        // a channel that is not a number is never close to anything
+        let diff = |a: f64, b: f64| match (a - b).abs() {
+            d if d.is_nan() => f64::INFINITY,
+            d => d,
+        };
+
+        self.pixels
+            .iter()
+            .zip(other.pixels.iter())
+            .map(|(a, b)| {
+                diff(a.red, b.red)
+                    .max(diff(a.green, b.green))
+                    .max(diff(a.blue, b.blue))
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// Panics unless every channel of every pixel of this canvas is within
+    /// `tol` of the same pixel of `other`, for comparing a render to a known
+    /// good image.
+    ///
+    /// Example
+    /// ```
+    /// use rustic_ray::{Canvas, Color};
+    ///
+    /// let a = Canvas::new(10, 20);
+    /// let mut b = Canvas::new(10, 20);
+    /// b.write_pixel(2, 3, Color::new(0.0, 0.001, 0.0));
+    ///
+    /// a.assert_close(&b, 0.01);
+    /// ```
+    pub fn assert_close(&self, other: &Canvas, tol: f64) {
+        let diff = self.max_channel_diff(other);
+        assert!(
+            diff <= tol,
+            "canvases differ by {} which is more than {}",
+            diff,
+            tol
+        );
+    }
 }
 
 #[cfg(test)]
@@ -219,4 +526,231 @@ mod tests {
             split[6]
         );
     }
+
+    #[test]
+    fn a_16_bit_ppm_scales_the_samples_to_65535() {
+        let mut c = Canvas::new(2, 1);
+        c.write_pixel(0, 0, Color::new(0.5, 0.5, 0.5));
+        c.write_pixel(1, 0, Color::new(1.5, 0.0, -0.5));
+        let actual = c.canvas_to_ppm_bits(16);
+        let split = actual.split('\n').collect::<Vec<_>>();
+
+        assert_eq!("65535", split[2]);
+        assert_eq!("32768 32768 32768 65535 0 0", split[3]);
+    }
+
+    #[test]
+    fn an_8_bit_ppm_matches_the_default_ppm() {
+        let mut c = Canvas::new(3, 2);
+        c.write_pixel(0, 0, Color::new(1.5, 0.0, 0.0));
+        c.write_pixel(2, 1, Color::new(0.0, 0.5, 0.4));
+
+        assert_eq!(c.canvas_to_ppm_bits(8), c.canvas_to_ppm());
+    }
+
+    #[test]
+    #[should_panic]
+    fn a_ppm_with_an_unsupported_sample_depth_panics() {
+        Canvas::new(1, 1).canvas_to_ppm_bits(12);
+    }
+
+    fn numbered_canvas(width: usize, height: usize) -> Canvas {
+        let mut c = Canvas::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                c.write_pixel(x, y, Color::new(x as f64, y as f64, 0.0));
+            }
+        }
+        c
+    }
+
+    #[test]
+    fn cropping_the_center_of_a_canvas() {
+        let c = numbered_canvas(4, 4);
+        let region = c.sub_region(1, 1, 2, 2);
+
+        assert_eq!(2, region.width);
+        assert_eq!(2, region.height);
+        assert_eq!(region.pixel_at(0, 0), Color::new(1.0, 1.0, 0.0));
+        assert_eq!(region.pixel_at(1, 0), Color::new(2.0, 1.0, 0.0));
+        assert_eq!(region.pixel_at(0, 1), Color::new(1.0, 2.0, 0.0));
+        assert_eq!(region.pixel_at(1, 1), Color::new(2.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn cropping_past_the_edge_of_a_canvas() {
+        let c = numbered_canvas(4, 4);
+        let region = c.sub_region(3, 2, 2, 3);
+
+        assert_eq!(region.pixel_at(0, 0), Color::new(3.0, 2.0, 0.0));
+        assert_eq!(region.pixel_at(0, 1), Color::new(3.0, 3.0, 0.0));
+        assert_eq!(region.pixel_at(1, 0), Color::new(0.0, 0.0, 0.0));
+        assert_eq!(region.pixel_at(0, 2), Color::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn blitting_a_region_into_a_canvas_at_an_offset() {
+        let region = numbered_canvas(4, 4).sub_region(1, 1, 2, 2);
+        let mut c = Canvas::new(4, 4);
+        c.blit(&region, 2, 0);
+
+        for y in 0..4 {
+            for x in 0..4 {
+                let expected = if x >= 2 && y < 2 {
+                    Color::new((x - 1) as f64, (y + 1) as f64, 0.0)
+                } else {
+                    Color::new(0.0, 0.0, 0.0)
+                };
+                assert_eq!(c.pixel_at(x, y), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn blitting_clips_at_the_edges_of_the_canvas() {
+        let region = numbered_canvas(4, 4);
+        let mut c = Canvas::new(3, 3);
+        c.blit(&region, 2, 1);
+
+        assert_eq!(c.pixel_at(2, 1), Color::new(0.0, 0.0, 0.0));
+        assert_eq!(c.pixel_at(2, 2), Color::new(0.0, 1.0, 0.0));
+        assert_eq!(c.pixel_at(1, 2), Color::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn tone_mapping_a_bright_pixel() {
+        let mut c = Canvas::new(5, 3);
+        c.write_pixel(1, 2, Color::new(4.0, 2.0, 0.0));
+        c.write_pixel(3, 0, Color::new(-0.5, 0.25, 1.0));
+        let mapped = c.tone_map_reinhard();
+
+        assert_eq!(mapped.pixel_at(1, 2), Color::new(0.8, 0.66667, 0.0));
+        assert_eq!(mapped.pixel_at(3, 0), Color::new(0.0, 0.2, 0.5));
+        assert_eq!(mapped.pixel_at(0, 0), Color::new(0.0, 0.0, 0.0));
+        assert_eq!(c.pixel_at(1, 2), Color::new(4.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn iterating_the_pixels_of_a_canvas() {
+        let c = numbered_canvas(3, 2);
+        let pixels: Vec<_> = c.iter_pixels().collect();
+
+        assert_eq!(pixels.len(), 6);
+        for (x, y, p) in pixels {
+            assert_eq!(p, c.pixel_at(x, y));
+        }
+    }
+
+    #[test]
+    fn mapping_pixels_to_their_complements() {
+        let mut c = Canvas::new(2, 2);
+        c.write_pixel(0, 0, Color::new(1.0, 0.0, 0.0));
+        c.write_pixel(1, 0, Color::new(0.0, 1.0, 0.0));
+        c.write_pixel(0, 1, Color::new(0.25, 0.5, 0.75));
+        let inverted = c.map_pixels(|_, _, p| Color::new(1.0 - p.red, 1.0 - p.green, 1.0 - p.blue));
+
+        assert_eq!(inverted.pixel_at(0, 0), Color::new(0.0, 1.0, 1.0));
+        assert_eq!(inverted.pixel_at(1, 0), Color::new(1.0, 0.0, 1.0));
+        assert_eq!(inverted.pixel_at(0, 1), Color::new(0.75, 0.5, 0.25));
+        assert_eq!(inverted.pixel_at(1, 1), Color::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn blurring_spreads_a_bright_pixel_to_its_neighbors() {
+        let mut c = Canvas::new(9, 9);
+        c.write_pixel(4, 4, Color::new(1.0, 0.5, 0.25));
+        let blurred = c.blur(2);
+
+        let total = |c: &Canvas| {
+            c.iter_pixels()
+                .fold(Color::new(0.0, 0.0, 0.0), |sum, (_, _, p)| sum + p)
+        };
+        assert_eq!(total(&blurred), total(&c));
+        assert_eq!(blurred.pixel_at(4, 4), Color::new(0.04, 0.02, 0.01));
+        assert_eq!(blurred.pixel_at(2, 6), Color::new(0.04, 0.02, 0.01));
+        assert_eq!(blurred.pixel_at(1, 4), Color::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn blurring_averages_only_the_pixels_inside_the_canvas() {
+        let mut c = Canvas::new(3, 1);
+        c.write_pixel(0, 0, Color::new(1.0, 1.0, 1.0));
+        let blurred = c.blur(1);
+
+        assert_eq!(blurred.pixel_at(0, 0), Color::new(0.5, 0.5, 0.5));
+        assert_eq!(
+            blurred.pixel_at(1, 0),
+            Color::new(0.33333, 0.33333, 0.33333)
+        );
+        assert_eq!(blurred.pixel_at(2, 0), Color::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn identical_canvases_have_no_difference() {
+        let c = numbered_canvas(4, 3);
+
+        assert_eq!(c.max_channel_diff(&c.clone()), 0.0);
+        c.assert_close(&c.clone(), 0.0);
+    }
+
+    #[test]
+    fn the_difference_of_canvases_is_the_changed_pixel_delta() {
+        let a = numbered_canvas(4, 3);
+        let mut b = a.clone();
+        let p = a.pixel_at(2, 1);
+        b.write_pixel(2, 1, Color::new(p.red, p.green + 0.3, p.blue - 0.1));
+
+        assert!((a.max_channel_diff(&b) - 0.3).abs() < 1e-12);
+        a.assert_close(&b, 0.31);
+    }
+
+    #[test]
+    #[should_panic]
+    fn canvases_beyond_the_tolerance_are_not_close() {
+        let a = Canvas::new(2, 2);
+        let mut b = Canvas::new(2, 2);
+        b.write_pixel(1, 1, Color::new(0.5, 0.0, 0.0));
+
+        a.assert_close(&b, 0.1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn comparing_canvases_of_different_sizes_panics() {
+        Canvas::new(2, 2).max_channel_diff(&Canvas::new(2, 3));
+    }
+
+    #[test]
+    fn a_channel_that_is_not_a_number_is_infinitely_different() {
+        let mut a = Canvas::new(2, 2);
+        a.write_pixel(1, 0, Color::new(0.0, f64::NAN, 0.0));
+
+        assert_eq!(a.max_channel_diff(&Canvas::new(2, 2)), f64::INFINITY);
+        assert_eq!(a.max_channel_diff(&a.clone()), f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic]
+    fn a_canvas_of_nan_pixels_is_not_close() {
+        let c = Canvas::new(2, 2).map_pixels(|_, _, _| Color::new(f64::NAN, f64::NAN, f64::NAN));
+
+        c.assert_close(&Canvas::new(2, 2), 1.0);
+    }
+
+    #[test]
+    fn texture_coordinates_outside_the_canvas_are_clamped_to_the_edge() {
+        let mut c = Canvas::new(10, 20);
+        c.write_pixel(9, 0, Color::new(1.0, 0.0, 0.0));
+        c.write_pixel(0, 19, Color::new(0.0, 1.0, 0.0));
+
+        assert_eq!(c.pixel_at_uv(1.5, 1.0), Color::new(1.0, 0.0, 0.0));
+        assert_eq!(c.pixel_at_uv(-0.5, -0.2), Color::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn texture_coordinates_on_an_empty_canvas_are_black() {
+        let c = Canvas::new(0, 0);
+
+        assert_eq!(c.pixel_at_uv(0.5, 0.5), Color::new(0.0, 0.0, 0.0));
+    }
 }
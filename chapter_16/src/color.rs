@@ -76,6 +76,61 @@ impl Color {
             format!("{}", (self.blue.clamp(0.0, 1.0) * 256.0) as u8),
         ]
     }
+
+    /// Blend `self` with `other`, `t` of `0.0` is `self` and `t` of `1.0` is
+    /// `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::Color;
+    ///
+    /// let a = Color::new(0.0, 0.5, 1.0);
+    /// let b = Color::new(1.0, 0.5, 0.0);
+    ///
+    /// assert_eq!(a.lerp(b, 0.25), Color::new(0.25, 0.5, 0.75));
+    /// ```
+    pub fn lerp(&self, other: Color, t: f64) -> Color {
+        *self + (other - *self) * t
+    }
+
+    /// The smaller of each of the red, green, and blue of `self` and `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::Color;
+    ///
+    /// let c = Color::new(1.0, 0.2, 0.7).min(Color::new(0.5, 0.6, 0.7));
+    ///
+    /// assert_eq!(c, Color::new(0.5, 0.2, 0.7));
+    /// ```
+    pub fn min(&self, other: Color) -> Color {
+        Color::new(
+            self.red.min(other.red),
+            self.green.min(other.green),
+            self.blue.min(other.blue),
+        )
+    }
+
+    /// The larger of each of the red, green, and blue of `self` and `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::Color;
+    ///
+    /// let c = Color::new(1.0, 0.2, 0.7).max(Color::new(0.5, 0.6, 0.7));
+    ///
+    /// assert_eq!(c, Color::new(1.0, 0.6, 0.7));
+    /// ```
+    pub fn max(&self, other: Color) -> Color {
+        Color::new(
+            self.red.max(other.red),
+            self.green.max(other.green),
+            self.blue.max(other.blue),
+        )
+    }
 }
 
 impl Sub for Color {
@@ -220,4 +275,23 @@ mod tests {
 
         assert_eq!(c1 * c2, Color::new(0.9, 0.2, 0.04));
     }
+
+    #[test]
+    fn lerp_halfway_averages_the_channels() {
+        let c1 = Color::new(1.0, 0.2, 0.4);
+        let c2 = Color::new(0.0, 0.6, 0.1);
+
+        assert_eq!(c1.lerp(c2, 0.5), Color::new(0.5, 0.4, 0.25));
+        assert_eq!(c1.lerp(c2, 0.0), c1);
+        assert_eq!(c1.lerp(c2, 1.0), c2);
+    }
+
+    #[test]
+    fn component_min_and_max_of_colors() {
+        let c1 = Color::new(1.0, 0.0, 0.0);
+        let c2 = Color::new(0.0, 1.0, 0.0);
+
+        assert_eq!(c1.max(c2), Color::new(1.0, 1.0, 0.0));
+        assert_eq!(c1.min(c2), Color::new(0.0, 0.0, 0.0));
+    }
 }
@@ -22,4 +22,64 @@ impl Colors {
         green: 0.0,
         blue: 0.0,
     };
+    /// The color green.
+    pub const GREEN: Color = Color {
+        red: 0.0,
+        green: 1.0,
+        blue: 0.0,
+    };
+    /// The color blue.
+    pub const BLUE: Color = Color {
+        red: 0.0,
+        green: 0.0,
+        blue: 1.0,
+    };
+    /// The color yellow.
+    pub const YELLOW: Color = Color {
+        red: 1.0,
+        green: 1.0,
+        blue: 0.0,
+    };
+    /// The color cyan.
+    pub const CYAN: Color = Color {
+        red: 0.0,
+        green: 1.0,
+        blue: 1.0,
+    };
+    /// The color magenta.
+    pub const MAGENTA: Color = Color {
+        red: 1.0,
+        green: 0.0,
+        blue: 1.0,
+    };
+    /// The color gray, halfway between black and white.
+    pub const GRAY: Color = Color {
+        red: 0.5,
+        green: 0.5,
+        blue: 0.5,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_palette_colors_match_their_definitions() {
+        let data = vec![
+            (Colors::BLACK, Color::new(0.0, 0.0, 0.0)),
+            (Colors::WHITE, Color::new(1.0, 1.0, 1.0)),
+            (Colors::RED, Color::new(1.0, 0.0, 0.0)),
+            (Colors::GREEN, Color::new(0.0, 1.0, 0.0)),
+            (Colors::BLUE, Color::new(0.0, 0.0, 1.0)),
+            (Colors::YELLOW, Color::new(1.0, 1.0, 0.0)),
+            (Colors::CYAN, Color::new(0.0, 1.0, 1.0)),
+            (Colors::MAGENTA, Color::new(1.0, 0.0, 1.0)),
+            (Colors::GRAY, Color::new(0.5, 0.5, 0.5)),
+        ];
+
+        for (color, expected) in data {
+            assert_eq!(color, expected);
+        }
+    }
 }
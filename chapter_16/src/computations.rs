@@ -1,6 +1,4 @@
-#[allow(unused_imports)]
-use crate::Intersection;
-use crate::{shapes::Shape, Point, Vector};
+use crate::{shapes::Shape, Color, Intersection, Point, Vector};
 
 /// Encapsulating precomputed information relating to an [`Intersection`].
 pub struct Computations<'a> {
@@ -30,6 +28,15 @@ pub struct Computations<'a> {
     /// The distance from the origin of a refractive ray to the point it
     /// enters a material
     pub n2: f64,
+    /// `u` property of the intersection
+    pub u: Option<f64>,
+    /// `v` property of the intersection
+    pub v: Option<f64>,
+    /// The intersection hit the back of a single sided surface.
+    pub backface: bool,
+    /// The offsets from `point` to where the rays of the next pixel across and
+    /// down hit, when the ray carries differentials.
+    pub footprint: Option<(Vector, Vector)>,
 }
 
 impl Computations<'_> {
@@ -53,4 +60,27 @@ impl Computations<'_> {
         let r0 = ((self.n1 - self.n2) / (self.n1 + self.n2)).powf(2.0);
         r0 + (1.0 - r0) * (1.0 - cos).powf(5.0)
     }
+
+    /// The Schlick approximation for a metal, where the reflectance when
+    /// looking straight at the surface is the color `f0` instead of being
+    /// found from the refractive indices. The reflectance rises to white at
+    /// grazing angles.
+    pub fn schlick_metallic(&self, f0: Color) -> Color {
+        let cos = self.eyev.dot(self.normalv).max(0.0);
+        let f = (1.0 - cos).powf(5.0);
+
+        f0 + (Color::new(1.0, 1.0, 1.0) - f0) * f
+    }
+
+    /// The [`Intersection`] the computations were prepared for, such as to
+    /// give a pattern the `u` and `v` of the hit.
+    pub fn intersection(&self) -> Intersection<'_> {
+        Intersection {
+            t: self.t,
+            object: self.object,
+            u: self.u,
+            v: self.v,
+            backface: self.backface,
+        }
+    }
 }
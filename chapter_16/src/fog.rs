@@ -0,0 +1,60 @@
+use crate::Color;
+
+/// Fog filling the space between the eye and a surface, blending the color of
+/// a surface toward the `color` of the fog the further away the surface is.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Fog {
+    /// Color a surface fades to in the distance.
+    pub color: Color,
+    /// How thick the fog is, the higher the density the closer a surface
+    /// fades into the fog.
+    pub density: f64,
+}
+
+impl Fog {
+    /// Create a fog of the `color` and `density`.
+    pub fn new(color: Color, density: f64) -> Fog {
+        Fog { color, density }
+    }
+
+    /// How much of the fog is seen in front of a surface at a distance `t`,
+    /// from `0.0` for no fog to `1.0` when only the fog is seen.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Colors, Fog};
+    ///
+    /// let fog = Fog::new(Colors::WHITE, 0.5);
+    ///
+    /// assert_eq!(fog.amount(0.0), 0.0);
+    /// assert!(fog.amount(1.0) < fog.amount(2.0));
+    /// ```
+    pub fn amount(&self, t: f64) -> f64 {
+        1.0 - (-self.density * t).exp()
+    }
+
+    /// Blend the `color` of a surface at a distance `t` toward the color of
+    /// the fog.
+    pub fn apply(&self, color: Color, t: f64) -> Color {
+        color.lerp(self.color, self.amount(t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Colors;
+
+    #[test]
+    fn fog_blends_a_color_toward_the_fog_with_distance() {
+        let fog = Fog::new(Colors::WHITE, 1.0);
+
+        assert_eq!(fog.apply(Colors::BLACK, 0.0), Colors::BLACK);
+        assert_eq!(
+            fog.apply(Colors::BLACK, 2.0_f64.ln()),
+            Color::new(0.5, 0.5, 0.5)
+        );
+        assert_eq!(fog.apply(Colors::BLACK, 100.0), Colors::WHITE);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::Rng;
+use std::cell::Cell;
+use uuid::Uuid;
+
+thread_local! {
+    static SEQUENCE: Cell<Option<Rng>> = const { Cell::new(None) };
+}
+
+/// Give every shape and pattern created from now on, on the current thread,
+/// an id from a sequence started with `seed` instead of a random id. Starting
+/// the sequence again with the same `seed` and building the same scene gives
+/// every object the same id as before, which keeps ids stable between runs.
+///
+/// # Example
+///
+/// ```
+/// use rustic_ray::{use_random_ids, use_seeded_ids, World};
+///
+/// use_seeded_ids(42);
+/// let a = World::default();
+/// use_seeded_ids(42);
+/// let b = World::default();
+/// use_random_ids();
+///
+/// assert_eq!(
+///     a.objects().map(|o| o.id()).collect::<Vec<_>>(),
+///     b.objects().map(|o| o.id()).collect::<Vec<_>>()
+/// );
+/// ```
+pub fn use_seeded_ids(seed: u64) {
+    SEQUENCE.with(|s| s.set(Some(Rng::new(seed))));
+}
+
+/// Go back to giving every shape and pattern created on the current thread a
+/// random id, the default.
+pub fn use_random_ids() {
+    SEQUENCE.with(|s| s.set(None));
+}
+
+/// Create the id for a new object, the next id of the seeded sequence if there
+/// is one otherwise a random version 4 id.
+pub(crate) fn new_id() -> Uuid {
+    SEQUENCE.with(|s| match s.get() {
+        Some(mut rng) => {
+            let bits = (rng.next_u64() as u128) << 64 | rng.next_u64() as u128;
+            s.set(Some(rng));
+            // mark the id as a random, version 4, id like the ones it stands in for
+            Uuid::from_u128(bits & !(0xF << 76) & !(0x3 << 62) | 0x4 << 76 | 0x2 << 62)
+        }
+        None => Uuid::new_v4(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::World;
+
+    #[test]
+    fn seeded_ids_repeat_for_the_same_seed() {
+        use_seeded_ids(7);
+        let a = [new_id(), new_id()];
+        use_seeded_ids(7);
+        let b = [new_id(), new_id()];
+        use_seeded_ids(8);
+        let c = new_id();
+        use_random_ids();
+
+        assert_eq!(a, b);
+        assert_ne!(a[0], a[1]);
+        assert_ne!(a[0], c);
+    }
+
+    #[test]
+    fn default_worlds_built_with_seeded_ids_have_the_same_ids() {
+        let ids = |w: &World| w.objects().map(|o| o.id()).collect::<Vec<_>>();
+
+        use_seeded_ids(1);
+        let a = World::default();
+        use_seeded_ids(1);
+        let b = World::default();
+        use_random_ids();
+        let c = World::default();
+
+        assert_eq!(ids(&a), ids(&b));
+        assert_ne!(ids(&a), ids(&c));
+    }
+}
@@ -1,5 +1,6 @@
-use crate::{float_cmp, shapes::Shape, Computations, Ray, World, EPSILON};
-use std::cmp::Ordering;
+use crate::{float_cmp, float_eq, shapes::Shape, Computations, Ray, World, EPSILON};
+use std::{cmp::Ordering, ops::Index};
+use uuid::Uuid;
 
 /// Aggregate of the distance from a [`Ray`]'s origin and the object that was
 /// intersected by a [`Ray`] at that distance.
@@ -13,6 +14,9 @@ pub struct Intersection<'a> {
     pub u: Option<f64>,
     /// `v` property
     pub v: Option<f64>,
+    /// The ray hit the back of a single sided surface, such as a triangle.
+    /// Always `false` for shapes without a front and a back.
+    pub backface: bool,
 }
 
 impl<'a> Intersection<'a> {
@@ -37,6 +41,7 @@ impl<'a> Intersection<'a> {
             object,
             u: None,
             v: None,
+            backface: false,
         }
     }
 
@@ -61,17 +66,42 @@ impl<'a> Intersection<'a> {
             object,
             u: Some(u),
             v: Some(v),
+            backface: false,
         }
     }
 
     /// Compute information related to an `Intersection` returning the
     /// information as [`Computations].
+    ///
+    /// **The world `w` must be given when the object is inside of a
+    /// [`Group`](crate::shapes::Group), this panics when it is `None`.** See
+    /// the Panics section below.
+    ///
+    /// The `over_point` and `under_point` are moved off the surface by the
+    /// `shadow_bias` of the world `w`, or by [`EPSILON`] without a world.
+    ///
+    /// The world `w` is needed to find the normal of an object inside of a
+    /// [`Group`](crate::shapes::Group), the transforms of the groups above the
+    /// object are found by walking up its parents in the world. Without a
+    /// world only the transform of the object itself would be used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the object of the intersection has a parent and `w` is
+    /// `None`. The normal would be found with only the transform of the
+    /// object and be silently wrong, pass the world the object was
+    /// intersected in. `None` is only for objects that are not in a group.
     pub fn prepare_computations<'h>(
         &'h self,
         r: Ray,
         xs: &[Intersection],
         w: Option<&World>,
     ) -> Computations<'h> {
+        assert!(
+            w.is_some() || self.object.parent_id().is_none(),
+            "A world is needed to prepare the computations of an object in a group"
+        );
+
         let point = r.position(self.t);
         let mut normalv = self.object.normal_at(point, Some(self), w);
         let mut inside = false;
@@ -80,44 +110,55 @@ impl<'a> Intersection<'a> {
             normalv = -normalv;
         }
 
-        let over_point = point + normalv * EPSILON;
-        let under_point = point - normalv * EPSILON;
+        let bias = w.map_or(EPSILON, |w| w.shadow_bias);
+        let over_point = point + normalv * bias;
+        let under_point = point - normalv * bias;
 
         let reflectv = r.direction.reflect(normalv);
 
         let mut n1 = 0.0;
         let mut n2 = 0.0;
-        let mut container: Vec<&dyn Shape> = Vec::new();
-        for i in xs {
-            if i == self {
-                if container.is_empty() {
-                    n1 = 1.0;
-                } else if let Some(object) = container.last() {
-                    n1 = match w {
-                        Some(w) => w.get_object_material(*object).refractive_index,
-                        None => object.material().refractive_index,
-                    }
+        // `self` is found in `xs` by where it is in memory, another
+        // intersection with the same `t` and object is not mistaken for it.
+        // A copy of an intersection in `xs` is found by its value.
+        let index = xs
+            .iter()
+            .position(|i| std::ptr::eq(i, self))
+            .or_else(|| xs.iter().position(|i| i == self));
+        if let Some(index) = index {
+            // Surfaces that touch, such as two spheres side by side, are
+            // crossed all at once. `n1` is from before the first intersection
+            // at the same `t` and `n2` from after the last one.
+            let same_t = |i: &Intersection| float_eq(i.t, self.t);
+            let first = xs[..index]
+                .iter()
+                .rposition(|i| !same_t(i))
+                .map_or(0, |j| j + 1);
+            let last = xs[index..]
+                .iter()
+                .position(|i| !same_t(i))
+                .map_or(xs.len(), |j| index + j);
+            let refractive_index = |container: &[&dyn Shape]| match container.last() {
+                Some(object) => match w {
+                    Some(w) => w.get_object_material(*object).refractive_index,
+                    None => object.material().refractive_index,
+                },
+                None => 1.0,
+            };
+
+            let mut container: Vec<&dyn Shape> = Vec::new();
+            for (j, i) in xs[..last].iter().enumerate() {
+                if j == first {
+                    n1 = refractive_index(&container);
                 }
-            }
 
-            if container.contains(&i.object) {
-                container.retain(|o| *o != i.object);
-            } else {
-                container.push(i.object);
-            }
-
-            if i == self {
-                if container.is_empty() {
-                    n2 = 1.0;
-                } else if let Some(object) = container.last() {
-                    n2 = match w {
-                        Some(w) => w.get_object_material(*object).refractive_index,
-                        None => object.material().refractive_index,
-                    }
+                if container.contains(&i.object) {
+                    container.retain(|o| *o != i.object);
+                } else {
+                    container.push(i.object);
                 }
-
-                break;
             }
+            n2 = refractive_index(&container);
         }
 
         Computations {
@@ -132,6 +173,10 @@ impl<'a> Intersection<'a> {
             reflectv,
             n1,
             n2,
+            u: self.u,
+            v: self.v,
+            backface: self.backface,
+            footprint: r.footprint(self.t, normalv),
         }
     }
 }
@@ -160,7 +205,56 @@ impl Intersection<'_> {
     /// assert_eq!(*i, xs[1]);
     /// ```
     pub fn hit<'a>(xs: &'a [Intersection]) -> Option<&'a Intersection<'a>> {
-        xs.iter().filter(|x| x.t >= 0.0).min()
+        Intersection::hit_in_range(xs, 0.0, f64::INFINITY)
+    }
+
+    /// Identify the `hit` from a list of intersections the same as `hit` only
+    /// considering intersections with a `t` value between `t_min` and `t_max`
+    /// inclusive. Intersections behind the origin of the ray are never a hit.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Intersection, shapes::Sphere};
+    ///
+    /// let s = Sphere::new();
+    /// let i1 = Intersection::new(1.0, &s);
+    /// let i2 = Intersection::new(2.0, &s);
+    /// let xs = vec![i1, i2];
+    /// let i = Intersection::hit_in_range(&xs, 1.5, 3.0).expect("Intersection did not hit!");
+    ///
+    /// assert_eq!(*i, xs[1]);
+    /// ```
+    pub fn hit_in_range<'a>(
+        xs: &'a [Intersection],
+        t_min: f64,
+        t_max: f64,
+    ) -> Option<&'a Intersection<'a>> {
+        xs.iter()
+            .filter(|x| x.t >= 0.0 && x.t >= t_min && x.t <= t_max)
+            .min()
+    }
+
+    /// Identify the `hit` from a list of intersections the same as `hit`
+    /// ignoring the intersections with the object with the given `id`, such as
+    /// the object a secondary ray starts from.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Intersection, shapes::Shape, shapes::Sphere};
+    ///
+    /// let s1 = Sphere::new();
+    /// let s2 = Sphere::new();
+    /// let xs = vec![Intersection::new(1.0, &s1), Intersection::new(2.0, &s2)];
+    /// let i = Intersection::hit_excluding(&xs, s1.id()).expect("Intersection did not hit!");
+    ///
+    /// assert_eq!(*i, xs[1]);
+    /// ```
+    pub fn hit_excluding<'a>(xs: &'a [Intersection], id: Uuid) -> Option<&'a Intersection<'a>> {
+        xs.iter()
+            .filter(|x| x.t >= 0.0 && x.object.id() != id)
+            .min()
     }
 }
 
@@ -172,15 +266,121 @@ impl PartialEq for Intersection<'_> {
 
 impl PartialOrd for Intersection<'_> {
     fn partial_cmp(&self, other: &Intersection) -> Option<Ordering> {
-        Some(float_cmp(self.t, other.t))
+        Some(self.cmp(other))
     }
 }
 
 impl Eq for Intersection<'_> {}
 
+/// Intersections are ordered by their `t` values. Intersections with the same
+/// `t`, such as where two surfaces overlap, are ordered by the `id` of their
+/// objects so the same one is always the `hit` no matter the order the
+/// objects were intersected in.
 impl Ord for Intersection<'_> {
     fn cmp(&self, other: &Self) -> Ordering {
-        float_cmp(self.t, other.t)
+        float_cmp(self.t, other.t).then_with(|| self.object.id().cmp(&other.object.id()))
+    }
+}
+
+/// A collection of [`Intersection`]s kept sorted by their `t` values.
+///
+/// # Example
+///
+/// ```
+/// use rustic_ray::{Intersection, Intersections, shapes::Sphere};
+///
+/// let s = Sphere::new();
+/// let mut xs = Intersections::new();
+/// xs.push_sorted(Intersection::new(2.0, &s));
+/// xs.push_sorted(Intersection::new(-1.0, &s));
+/// xs.push_sorted(Intersection::new(1.0, &s));
+///
+/// assert_eq!(xs.len(), 3);
+/// assert_eq!(xs[0].t, -1.0);
+/// assert_eq!(xs.hit().unwrap().t, 1.0);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Intersections<'a>(Vec<Intersection<'a>>);
+
+impl<'a> Intersections<'a> {
+    /// Create an empty collection of intersections.
+    pub fn new() -> Intersections<'a> {
+        Intersections(Vec::new())
+    }
+
+    /// Insert an intersection keeping the collection sorted. An intersection
+    /// with the same `t` and object as others is placed after them.
+    pub fn push_sorted(&mut self, i: Intersection<'a>) {
+        let index = self.0.partition_point(|x| *x <= i);
+        self.0.insert(index, i);
+    }
+
+    /// The number of intersections in the collection.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no intersections in the collection.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The intersection with the lowest nonnegative `t` value, see
+    /// [`Intersection::hit`].
+    pub fn hit(&self) -> Option<&Intersection<'_>> {
+        Intersection::hit(&self.0)
+    }
+
+    /// Iterate over the intersections in order.
+    pub fn iter(&self) -> std::slice::Iter<'_, Intersection<'a>> {
+        self.0.iter()
+    }
+}
+
+impl<'a> Index<usize> for Intersections<'a> {
+    type Output = Intersection<'a>;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<'a> From<Vec<Intersection<'a>>> for Intersections<'a> {
+    fn from(mut xs: Vec<Intersection<'a>>) -> Self {
+        xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        Intersections(xs)
+    }
+}
+
+impl<'a> FromIterator<Intersection<'a>> for Intersections<'a> {
+    fn from_iter<I: IntoIterator<Item = Intersection<'a>>>(iter: I) -> Self {
+        Intersections::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<'a> IntoIterator for Intersections<'a> {
+    type Item = Intersection<'a>;
+    type IntoIter = std::vec::IntoIter<Intersection<'a>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> From<Intersections<'a>> for Vec<Intersection<'a>> {
+    fn from(xs: Intersections<'a>) -> Self {
+        xs.0
+    }
+}
+
+/// Intersections as returned by `intersect`, `None` when there are none.
+impl<'a> From<Intersections<'a>> for Option<Vec<Intersection<'a>>> {
+    fn from(xs: Intersections<'a>) -> Self {
+        if xs.is_empty() {
+            None
+        } else {
+            Some(xs.0)
+        }
     }
 }
 
@@ -188,8 +388,8 @@ impl Ord for Intersection<'_> {
 mod tests {
     use super::*;
     use crate::{
-        float_eq, shapes::Plane, shapes::Sphere, shapes::Triangle, Point, Ray, Transformation,
-        Vector, EPSILON,
+        float_eq, shapes::Group, shapes::Plane, shapes::Sphere, shapes::Triangle, Point, Ray,
+        Transformation, Vector, EPSILON,
     };
 
     // Chapter 5 Ray-Sphere Intersections
@@ -267,6 +467,31 @@ mod tests {
         assert!(i.is_none());
     }
 
+    #[test]
+    fn the_hit_in_range_skips_intersections_past_t_max() {
+        let s = Sphere::new();
+        let i1 = Intersection::new(5.0, &s);
+        let i2 = Intersection::new(-1.0, &s);
+        let i3 = Intersection::new(3.0, &s);
+        let xs = vec![i1, i2, i3];
+
+        assert!(Intersection::hit_in_range(&xs, 0.0, 2.0).is_none());
+        let i = Intersection::hit_in_range(&xs, 0.0, 4.0).expect("Intersection did not hit!");
+        assert_eq!(*i, xs[2]);
+    }
+
+    #[test]
+    fn the_hit_in_range_returns_the_next_intersection_past_t_min() {
+        let s = Sphere::new();
+        let i1 = Intersection::new(5.0, &s);
+        let i2 = Intersection::new(7.0, &s);
+        let i3 = Intersection::new(3.0, &s);
+        let xs = vec![i1, i2, i3];
+        let i = Intersection::hit_in_range(&xs, 4.0, 10.0).expect("Intersection did not hit!");
+
+        assert_eq!(*i, xs[0]);
+    }
+
     // Chapter 5 Ray-Sphere Intersections
     // Page 66
     #[test]
@@ -402,6 +627,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn finding_n1_n2_between_two_touching_spheres() {
+        let mut a = Sphere::glass_sphere();
+        a.transform = Transformation::new().translate(0.0, 0.0, -1.0).build();
+        a.material.refractive_index = 1.5;
+        let mut b = Sphere::glass_sphere();
+        b.transform = Transformation::new().translate(0.0, 0.0, 1.0).build();
+        b.material.refractive_index = 2.0;
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        // the ray leaves `a` and enters `b` at the same point, in either order
+        for xs in [
+            vec![
+                Intersection::new(3.0, &a),
+                Intersection::new(5.0, &a),
+                Intersection::new(5.0, &b),
+                Intersection::new(7.0, &b),
+            ],
+            vec![
+                Intersection::new(3.0, &a),
+                Intersection::new(5.0, &b),
+                Intersection::new(5.0, &a),
+                Intersection::new(7.0, &b),
+            ],
+        ] {
+            let expected = [(1.0, 1.5), (1.5, 2.0), (1.5, 2.0), (2.0, 1.0)];
+            for (i, (n1, n2)) in expected.iter().enumerate() {
+                let comps = xs[i].prepare_computations(r, &xs, None);
+                assert_eq!((comps.n1, comps.n2), (*n1, *n2));
+            }
+        }
+    }
+
     // Chapter 11 Reflection and Refraction
     // Page 154
     #[test]
@@ -484,4 +742,95 @@ mod tests {
         assert_eq!(i.u.unwrap(), 0.2);
         assert_eq!(i.v.unwrap(), 0.4);
     }
+
+    #[test]
+    fn push_sorted_keeps_intersections_in_order() {
+        let s = Sphere::new();
+        let mut xs = Intersections::new();
+        for t in [5.0, -3.0, 7.0, 2.0, 0.0, 2.0] {
+            xs.push_sorted(Intersection::new(t, &s));
+        }
+
+        assert_eq!(xs.len(), 6);
+        let ts = xs.iter().map(|i| i.t).collect::<Vec<_>>();
+        assert_eq!(ts, vec![-3.0, 0.0, 2.0, 2.0, 5.0, 7.0]);
+    }
+
+    #[test]
+    fn the_hit_of_intersections_is_the_lowest_nonnegative_t() {
+        let s = Sphere::new();
+        let xs: Intersections = vec![
+            Intersection::new(5.0, &s),
+            Intersection::new(7.0, &s),
+            Intersection::new(-3.0, &s),
+            Intersection::new(2.0, &s),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(xs[0].t, -3.0);
+        assert_eq!(xs.hit().unwrap().t, 2.0);
+        assert!(Intersections::new().hit().is_none());
+    }
+
+    #[test]
+    fn converting_intersections_to_an_optional_vec() {
+        let s = Sphere::new();
+        let none: Option<Vec<Intersection>> = Intersections::new().into();
+        let xs: Intersections = vec![Intersection::new(2.0, &s), Intersection::new(1.0, &s)].into();
+        let some: Option<Vec<Intersection>> = xs.into();
+
+        assert!(none.is_none());
+        assert_eq!(some.unwrap()[0].t, 1.0);
+    }
+
+    fn world_with_a_grouped_sphere() -> World {
+        let mut g = Group::new();
+        g.set_transform(Transformation::new().scale(1.0, 2.0, 1.0).build());
+        let mut s = Sphere::new();
+        s.set_transform(Transformation::new().translate(5.0, 0.0, 0.0).build());
+        g.add_object(Box::new(s));
+        let mut w = World::new();
+        w.add_object(Box::new(g));
+        w
+    }
+
+    #[test]
+    #[should_panic(expected = "A world is needed")]
+    fn preparing_computations_for_a_grouped_object_needs_a_world() {
+        let w = world_with_a_grouped_sphere();
+        let r = Ray::new(Point::new(5.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = w.intersect_world(r).unwrap();
+
+        xs[0].prepare_computations(r, &xs, None);
+    }
+
+    #[test]
+    fn preparing_computations_for_a_grouped_object_with_a_world() {
+        let w = world_with_a_grouped_sphere();
+        let r = Ray::new(Point::new(5.5, 1.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = w.intersect_world(r).unwrap();
+        let comps = xs[0].prepare_computations(r, &xs, Some(&w));
+        let expected = xs[0].object.normal_at(comps.point, Some(&xs[0]), Some(&w));
+
+        assert_eq!(comps.normalv, expected);
+        assert!(comps.normalv.y > 0.0);
+    }
+
+    #[test]
+    fn the_hit_excluding_the_originating_sphere_is_the_next_object() {
+        let s1 = Sphere::new();
+        let mut s2 = Sphere::new();
+        s2.transform = Transformation::new().translate(0.0, 0.0, -5.0).build();
+        let r = Ray::new(Point::new(0.0, 0.0, -1.0), Vector::new(0.0, 0.0, -1.0));
+        let mut xs = s1.intersect(r).unwrap();
+        xs.extend(s2.intersect(r).unwrap());
+
+        assert_eq!(Intersection::hit(&xs).unwrap().t, 0.0);
+
+        let i = Intersection::hit_excluding(&xs, s1.id()).unwrap();
+
+        assert_eq!(i.t, 3.0);
+        assert!(i.object.shape_eq(&s2));
+    }
 }
@@ -1,39 +1,56 @@
 //! Rustic Ray is as ray tracer library based on the book The Ray Tracer
 //! Challenge by Jamis Buck
+mod bounds;
+mod bvh;
 mod camera;
 mod canvas;
 mod color;
 mod colors;
 mod computations;
+mod fog;
+mod id;
 mod intersection;
 mod light;
 mod material;
 mod matrix;
+mod matrix32;
 mod obj_file;
 pub mod patterns;
 mod point;
 mod ray;
+mod rng;
+mod scene;
 pub mod shapes;
+mod skybox;
+#[cfg(feature = "stats")]
+mod stats;
 mod transformation;
 mod vector;
 mod world;
 
+pub use crate::bounds::Bounds;
 pub use crate::camera::Camera;
 pub use crate::canvas::Canvas;
 pub use crate::color::Color;
 pub use crate::colors::Colors;
 pub use crate::computations::Computations;
-pub use crate::intersection::Intersection;
+pub use crate::fog::Fog;
+pub use crate::id::{use_random_ids, use_seeded_ids};
+pub use crate::intersection::{Intersection, Intersections};
 pub use crate::light::PointLight;
-pub use crate::material::Material;
+pub use crate::material::{Material, ShadingModel};
 pub use crate::matrix::Matrix;
 pub use crate::matrix::IDENTITY;
+pub use crate::matrix32::Matrix32;
 pub use crate::obj_file::ObjFile;
 pub use crate::point::Point;
 pub use crate::ray::Ray;
+pub use crate::rng::Rng;
+pub use crate::scene::{Scene, SceneError};
+pub use crate::skybox::Skybox;
 pub use crate::transformation::Transformation;
 pub use crate::vector::Vector;
-pub use crate::world::World;
+pub use crate::world::{World, WorldBuilder};
 
 use std::cmp::Ordering;
 
@@ -43,7 +60,22 @@ pub const EPSILON: f64 = 0.0001;
 /// Compare two floating point numbers to determine if they are
 /// approximately equal
 pub fn float_eq(a: f64, b: f64) -> bool {
-    (a - b).abs() < EPSILON
+    float_eq_eps(a, b, EPSILON)
+}
+
+/// Compare two floating point numbers to determine if they are
+/// approximately equal within `eps` instead of [`EPSILON`].
+///
+/// # Example
+///
+/// ```
+/// use rustic_ray::{float_eq, float_eq_eps};
+///
+/// assert!(float_eq(1.0, 1.00005));
+/// assert!(!float_eq_eps(1.0, 1.00005, 0.000001));
+/// ```
+pub fn float_eq_eps(a: f64, b: f64, eps: f64) -> bool {
+    (a - b).abs() < eps
 }
 
 /// Multiple two 4x4 arrays
@@ -1,16 +1,39 @@
-use crate::{Color, Point};
+use crate::{Color, Point, Rng, Vector};
+use std::f64::consts::PI;
 
 /// A light source with no size, existing at a single point in space.
 ///
 /// A `PointLight` is defined by its position in space and the intensity or how
 /// bright the light it is. The intensity also describes the color of the
 /// light source.
+///
+/// The intensity of a `PointLight` can fall off with distance. The light
+/// reaching a point `d` units away is divided by
+/// `constant + linear * d + quadratic * d²`. By default there is no
+/// attenuation.
+///
+/// A `PointLight` with a `radius` casts soft shadows, the light is checked
+/// from `samples` positions spread over a disc of that radius. By default the
+/// radius is `0.0` and shadows are hard.
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct PointLight {
     /// Brightness and color of the light
     pub intensity: Color,
     /// Position in space
     pub position: Point,
+    /// Constant term of the attenuation
+    pub constant: f64,
+    /// Linear term of the attenuation
+    pub linear: f64,
+    /// Quadratic term of the attenuation
+    pub quadratic: f64,
+    /// Radius of the disc the light is sampled over for soft shadows
+    pub radius: f64,
+    /// Number of positions sampled over the disc of the `radius`
+    pub samples: usize,
+    /// Random number generator used to turn the positions sampled over the
+    /// disc, the same seed gives the same soft shadows
+    pub rng: Rng,
 }
 
 impl PointLight {
@@ -33,10 +56,103 @@ impl PointLight {
         PointLight {
             position,
             intensity,
+            constant: 1.0,
+            linear: 0.0,
+            quadratic: 0.0,
+            radius: 0.0,
+            samples: 1,
+            rng: Rng::default(),
+        }
+    }
+
+    /// Set the `constant`, `linear` and `quadratic` terms used to attenuate
+    /// the light with distance.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Color, PointLight, Point};
+    ///
+    /// let light = PointLight::new(Point::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0))
+    ///     .with_attenuation(1.0, 0.0, 1.0);
+    ///
+    /// assert_eq!(light.attenuation(3.0), 10.0);
+    /// ```
+    pub fn with_attenuation(mut self, constant: f64, linear: f64, quadratic: f64) -> PointLight {
+        self.constant = constant;
+        self.linear = linear;
+        self.quadratic = quadratic;
+        self
+    }
+
+    /// The amount the light is divided by at the given `distance` from the
+    /// light.
+    pub fn attenuation(&self, distance: f64) -> f64 {
+        self.constant + self.linear * distance + self.quadratic * distance.powi(2)
+    }
+
+    /// Give the light a `radius` to cast soft shadows, checking for shadows
+    /// from `samples` positions over a disc of the `radius`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Color, PointLight, Point};
+    ///
+    /// let light = PointLight::new(Point::new(0.0, 5.0, 0.0), Color::new(1.0, 1.0, 1.0))
+    ///     .with_radius(0.5, 16);
+    ///
+    /// assert_eq!(light.sample_positions(Point::new(0.0, 0.0, 0.0)).len(), 16);
+    /// ```
+    pub fn with_radius(mut self, radius: f64, samples: usize) -> PointLight {
+        self.radius = radius;
+        self.samples = samples;
+        self
+    }
+
+    /// The positions on the light a shadow is checked from for the `point`.
+    /// The positions are spread over a disc of the `radius` facing the
+    /// `point`, turned by a random angle picked from the `rng` and the
+    /// `point` so neighboring points don't share the same pattern. A light without a
+    /// radius is only at its `position`.
+    pub fn sample_positions(&self, point: Point) -> Vec<Point> {
+        if self.radius <= 0.0 || self.samples <= 1 {
+            return vec![self.position];
         }
+
+        // two directions across the disc facing the point
+        let n = (point - self.position).normalize();
+        let axis = if n.x.abs() < 0.9 {
+            Vector::new(1.0, 0.0, 0.0)
+        } else {
+            Vector::new(0.0, 1.0, 0.0)
+        };
+        let u = n.cross(axis).normalize();
+        let v = n.cross(u);
+
+        let mut rng = self.rng;
+        let mut rng = Rng::new(
+            rng.next_u64()
+                ^ point.x.to_bits()
+                ^ point.y.to_bits().rotate_left(21)
+                ^ point.z.to_bits().rotate_left(42),
+        );
+        let turn = rng.next_f64() * 2.0 * PI;
+
+        // a spiral of positions evenly covering the disc
+        (0..self.samples)
+            .map(|k| {
+                let r = self.radius * ((k as f64 + 0.5) / self.samples as f64).sqrt();
+                let theta = turn + k as f64 * GOLDEN_ANGLE;
+                self.position + u * (r * theta.cos()) + v * (r * theta.sin())
+            })
+            .collect()
     }
 }
 
+// The angle between positions of the spiral sampling the disc of a light.
+const GOLDEN_ANGLE: f64 = 2.399_963_229_728_653;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -53,4 +169,44 @@ mod tests {
         assert_eq!(light.position, position);
         assert_eq!(light.intensity, intensity);
     }
+
+    #[test]
+    fn a_point_light_has_no_attenuation_by_default() {
+        let light = PointLight::new(Point::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0));
+
+        assert_eq!(light.attenuation(0.0), 1.0);
+        assert_eq!(light.attenuation(100.0), 1.0);
+    }
+
+    #[test]
+    fn the_positions_of_a_light_with_a_radius_are_on_its_disc() {
+        let light = PointLight::new(Point::new(0.0, 5.0, 0.0), Color::new(1.0, 1.0, 1.0))
+            .with_radius(0.5, 8);
+        let point = Point::new(0.0, 0.0, 0.0);
+        let positions = light.sample_positions(point);
+
+        assert_eq!(positions.len(), 8);
+        assert_eq!(positions, light.sample_positions(point));
+        for p in positions {
+            assert!(crate::float_eq(p.y, 5.0));
+            assert!((p - light.position).magnitude() <= 0.5);
+        }
+        assert_eq!(
+            PointLight::new(light.position, light.intensity).sample_positions(point),
+            vec![light.position]
+        );
+    }
+
+    #[test]
+    fn the_seed_of_a_light_turns_the_positions_on_its_disc() {
+        let light = PointLight::new(Point::new(0.0, 5.0, 0.0), Color::new(1.0, 1.0, 1.0))
+            .with_radius(0.5, 8);
+        let point = Point::new(0.0, 0.0, 0.0);
+        let mut other = light;
+        other.rng.seed(7);
+
+        assert_ne!(light.sample_positions(point), other.sample_positions(point));
+        other.rng.seed(0);
+        assert_eq!(light.sample_positions(point), other.sample_positions(point));
+    }
 }
@@ -1,4 +1,8 @@
-use crate::{patterns::Pattern, shapes::Shape, Color, Point, PointLight, Vector};
+use crate::{
+    patterns::{Pattern, ScalarPattern},
+    shapes::Shape,
+    Color, Computations, Intersection, Point, PointLight, Vector, World,
+};
 
 /// Encapsulates the attributes from the Phong reflection model.
 ///
@@ -17,7 +21,7 @@ use crate::{patterns::Pattern, shapes::Shape, Color, Point, PointLight, Vector};
 /// highlight.
 ///
 /// Buck, Jamis "The Ray Tracer Challenge" (84)
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Material {
     /// Color of the material.
     pub color: Color,
@@ -33,15 +37,52 @@ pub struct Material {
     pub shininess: f64,
     /// Attribute which determines how *reflective* a object is.
     pub reflective: f64,
+    /// A metal reflects more light when seen at a grazing angle. The reflected
+    /// color is weighted by the Schlick approximation using the `color` of the
+    /// material instead of by `reflective`. Defaults to `false`.
+    pub metallic: bool,
+    /// Tint of the reflections, the reflected color is multiplied by this
+    /// color. Defaults to `None`, reflections are not tinted.
+    pub reflect_color: Option<Color>,
     /// Attribute which determines how *transparent* an object is.
     pub transparency: f64,
     /// Attribute to designate the index of refraction for an object.
     pub refractive_index: f64,
+    /// Color given off by the material itself regardless of any lighting.
+    pub emissive: Color,
+    /// Determine if the object blocks light and casts a shadow.
+    pub casts_shadow: bool,
     /// Apply an optional `pattern` for shape instead of a color.
     pub pattern: Option<Box<dyn Pattern>>,
+    /// Apply an optional pattern for the transparency of the shape instead of
+    /// `transparency`, such as the alpha of a leaf. Defaults to `None`.
+    pub transparency_map: Option<Box<dyn ScalarPattern>>,
+    /// How the diffuse light across the surface is shaded. Defaults to
+    /// [`ShadingModel::Phong`].
+    pub shading: ShadingModel,
+}
+
+/// How the diffuse light across the surface of a [`Material`] is shaded.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ShadingModel {
+    /// The diffuse light changes smoothly with the angle between the light
+    /// and the surface.
+    Phong,
+    /// The diffuse light steps between `bands` levels of brightness, for a
+    /// cartoon like look.
+    Toon { bands: usize },
 }
 
 impl Material {
+    /// Refractive index of a vacuum or air.
+    pub const AIR: f64 = 1.0;
+    /// Refractive index of water.
+    pub const WATER: f64 = 1.333;
+    /// Refractive index of glass.
+    pub const GLASS: f64 = 1.5;
+    /// Refractive index of diamond.
+    pub const DIAMOND: f64 = 2.417;
+
     /// Create a default material with
     /// ```text
     /// Color: red: 1.0, green: 1.0, blue 1.0
@@ -62,6 +103,7 @@ impl Material {
     /// assert_eq!(m.diffuse, 0.9);
     /// assert_eq!(m.specular, 0.9);
     /// assert_eq!(m.shininess, 200.0);
+    /// assert!(m.casts_shadow);
     /// ```
     pub fn new() -> Self {
         Material {
@@ -71,14 +113,146 @@ impl Material {
             specular: 0.9,
             shininess: 200.0,
             reflective: 0.0,
+            metallic: false,
+            reflect_color: None,
             refractive_index: 1.0,
             transparency: 0.0,
+            emissive: Color::new(0.0, 0.0, 0.0),
+            casts_shadow: true,
             pattern: None,
+            transparency_map: None,
+            shading: ShadingModel::Phong,
+        }
+    }
+
+    /// Create a clear glass material, fully transparent with the refractive
+    /// index of glass.
+    ///
+    /// # Example
+    /// ```
+    /// use rustic_ray::Material;
+    ///
+    /// let m = Material::glass();
+    ///
+    /// assert_eq!(m.transparency, 1.0);
+    /// assert_eq!(m.refractive_index, 1.5);
+    /// ```
+    pub fn glass() -> Self {
+        Material {
+            transparency: 1.0,
+            refractive_index: Material::GLASS,
+            ..Material::new()
+        }
+    }
+
+    /// Replace the refractive index of the material, such as with one of
+    /// [`Material::AIR`], [`Material::WATER`], [`Material::GLASS`], or
+    /// [`Material::DIAMOND`].
+    ///
+    /// # Example
+    /// ```
+    /// use rustic_ray::Material;
+    ///
+    /// let m = Material::glass().with_refraction(Material::WATER);
+    ///
+    /// assert_eq!(m.transparency, 1.0);
+    /// assert_eq!(m.refractive_index, 1.333);
+    /// ```
+    pub fn with_refraction(mut self, index: f64) -> Self {
+        self.refractive_index = index;
+        self
+    }
+
+    /// Create a mirror material, fully reflective with no ambient or diffuse
+    /// light of its own.
+    ///
+    /// # Example
+    /// ```
+    /// use rustic_ray::Material;
+    ///
+    /// let m = Material::mirror();
+    ///
+    /// assert_eq!(m.reflective, 1.0);
+    /// assert_eq!(m.diffuse, 0.0);
+    /// ```
+    pub fn mirror() -> Self {
+        Material {
+            ambient: 0.0,
+            diffuse: 0.0,
+            reflective: 1.0,
+            ..Material::new()
+        }
+    }
+
+    /// Create a matte material of the given `color` without a specular
+    /// highlight.
+    ///
+    /// # Example
+    /// ```
+    /// use rustic_ray::{Colors, Material};
+    ///
+    /// let m = Material::matte(Colors::RED);
+    ///
+    /// assert_eq!(m.color, Colors::RED);
+    /// assert_eq!(m.specular, 0.0);
+    /// ```
+    pub fn matte(color: Color) -> Self {
+        Material {
+            color,
+            specular: 0.0,
+            ..Material::new()
+        }
+    }
+
+    /// The transparency of the material at the world space `point` of the
+    /// `object`, the value of the `transparency_map` when there is one or
+    /// `transparency` when there is not. The world `w` is used to convert the
+    /// `point` to the space of an object nested in groups.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{patterns::ScalarCheckers, shapes::Sphere, Material, Point};
+    ///
+    /// let s = Sphere::new();
+    /// let mut m = Material::new();
+    /// m.transparency = 0.5;
+    ///
+    /// assert_eq!(m.transparency_at(&s, Point::new(1.5, 0.0, 0.0), None), 0.5);
+    ///
+    /// m.transparency_map = Some(Box::new(ScalarCheckers::new(0.0, 1.0)));
+    ///
+    /// assert_eq!(m.transparency_at(&s, Point::new(1.5, 0.0, 0.0), None), 1.0);
+    /// ```
+    pub fn transparency_at(&self, object: &dyn Shape, point: Point, w: Option<&World>) -> f64 {
+        match self.transparency_map.as_ref() {
+            Some(map) => map.value_at(object, point, w),
+            None => self.transparency,
+        }
+    }
+
+    /// The color of the material at the `point` of the `object`, from the
+    /// pattern of the material when it has one. The pattern is averaged over
+    /// the `footprint` of the pixel the point is seen through, see
+    /// [`Pattern::pattern_at_footprint`].
+    pub fn color_at(
+        &self,
+        object: &dyn Shape,
+        point: Point,
+        hit: Option<&Intersection>,
+        footprint: Option<(Vector, Vector)>,
+        w: Option<&World>,
+    ) -> Color {
+        match self.pattern.as_ref() {
+            Some(pattern) => pattern.pattern_at_footprint(object, point, hit, footprint, w),
+            None => self.color,
         }
     }
 
     /// Add together the material's ambient, diffuse, and specular components,
-    /// weighted by the angels between the different vectors.
+    /// weighted by the angels between the different vectors, along with the
+    /// `emissive` color of the material. The pattern of the material is found
+    /// at the `point` of the `object` alone, use `lighting_at` for a hit.
     pub fn lighting(
         &self,
         object: &dyn Shape,
@@ -88,15 +262,69 @@ impl Material {
         normalv: Vector,
         in_shadow: bool,
     ) -> Color {
-        let color = match self.pattern.as_ref() {
-            Some(pattern) => pattern.pattern_at_shape(object, point),
-            None => self.color,
-        };
+        let color = self.color_at(object, point, None, None, None);
+        self.lighting_color(color, light, point, eyev, normalv, in_shadow)
+    }
+
+    /// Light the hit the computations `comps` were prepared for, the same as
+    /// `lighting` at the `over_point` of the hit. The intersection is given to
+    /// the pattern for patterns that need the `u` and `v` of the hit, and the
+    /// world `w` to convert the point to the space of an object nested in
+    /// groups.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{shapes::Sphere, Color, Intersection, Material, Point, PointLight, Ray, Vector};
+    ///
+    /// let s = Sphere::new();
+    /// let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+    /// let xs = vec![Intersection::new(4.0, &s), Intersection::new(6.0, &s)];
+    /// let comps = xs[0].prepare_computations(r, &xs, None);
+    /// let light = PointLight::new(Point::new(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+    ///
+    /// let c = Material::new().lighting_at(&comps, light, false, None);
+    ///
+    /// assert_eq!(c, Color::new(1.9, 1.9, 1.9));
+    /// ```
+    pub fn lighting_at(
+        &self,
+        comps: &Computations,
+        light: PointLight,
+        in_shadow: bool,
+        w: Option<&World>,
+    ) -> Color {
+        let hit = comps.intersection();
+        let color = self.color_at(comps.object, comps.over_point, Some(&hit), None, w);
+        self.lighting_color(
+            color,
+            light,
+            comps.over_point,
+            comps.eyev,
+            comps.normalv,
+            in_shadow,
+        )
+    }
+
+    // Light the surface of the `color`, the same as `lighting` once the color
+    // at the point has been found.
+    pub(crate) fn lighting_color(
+        &self,
+        color: Color,
+        light: PointLight,
+        point: Point,
+        eyev: Vector,
+        normalv: Vector,
+        in_shadow: bool,
+    ) -> Color {
         // combine the surface color with the light's color/intensity
         let effective_color = color * light.intensity;
 
-        // find the direction to the light source
-        let lightv = (light.position - point).normalize();
+        // find the direction to the light source and how much the light
+        // has fallen off over that distance
+        let lightv = light.position - point;
+        let falloff = 1.0 / light.attenuation(lightv.magnitude());
+        let lightv = lightv.normalize();
 
         // compute the ambient contribution
         let ambient = effective_color * self.ambient;
@@ -111,8 +339,16 @@ impl Material {
             diffuse = Color::new(0.0, 0.0, 0.0);
             specular = Color::new(0.0, 0.0, 0.0);
         } else {
-            // compute the diffuse contribution
-            diffuse = effective_color * self.diffuse * light_dot_normal;
+            // compute the diffuse contribution, a toon material rounds the
+            // angle up to the next of its bands
+            let light_dot_normal = match self.shading {
+                ShadingModel::Phong => light_dot_normal,
+                ShadingModel::Toon { bands } => {
+                    let bands = bands.max(1) as f64;
+                    (light_dot_normal * bands).ceil() / bands
+                }
+            };
+            diffuse = effective_color * self.diffuse * light_dot_normal * falloff;
 
             // reflect_dot_eye represents the cosine of teh the angle between the
             // reflection vector and the eye vector. A negative number means the
@@ -124,12 +360,13 @@ impl Material {
             } else {
                 // Compute the specular contribution
                 let factor = reflect_dot_eye.powf(self.shininess);
-                specular = light.intensity * self.specular * factor;
+                specular = light.intensity * self.specular * factor * falloff;
             }
         }
 
-        // Add teh three contributions together to get the final shading
-        ambient + diffuse + specular
+        // Add teh three contributions together with the light given off by
+        // the material to get the final shading
+        ambient + diffuse + specular + self.emissive
     }
 }
 
@@ -141,7 +378,9 @@ impl Default for Material {
 
 #[cfg(test)]
 mod tests {
-    use crate::{patterns::Stripe, shapes::Sphere, Colors, Point, PointLight, Vector};
+    use crate::{
+        patterns::Stripe, shapes::Shape, shapes::Sphere, Colors, Point, PointLight, Vector,
+    };
 
     use super::*;
 
@@ -276,6 +515,72 @@ mod tests {
         assert_eq!(c2, Colors::BLACK);
     }
 
+    #[test]
+    fn a_cloned_material_has_the_same_pattern() {
+        let mut m = Material::new();
+        m.pattern = Some(Box::new(Stripe::new(Colors::WHITE, Colors::BLACK)));
+        m.reflective = 0.5;
+        let clone = m.clone();
+        let object = Sphere::new();
+
+        assert_eq!(clone, m);
+        assert_eq!(clone.reflective, 0.5);
+        for x in [0.0, 0.5, 1.0, 1.5, -0.5] {
+            let point = Point::new(x, 0.0, 0.0);
+            assert_eq!(
+                clone.color_at(&object, point, None, None, None),
+                m.color_at(&object, point, None, None, None)
+            );
+        }
+        assert_eq!(
+            clone.color_at(&object, Point::new(1.0, 0.0, 0.0), None, None, None),
+            Colors::BLACK
+        );
+    }
+
+    #[test]
+    fn lighting_a_farther_surface_with_quadratic_attenuation() {
+        let mut m = Material::new();
+        m.ambient = 0.0;
+        m.specular = 0.0;
+        let eyev = Vector::new(0.0, 0.0, -1.0);
+        let normalv = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Point::new(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0))
+            .with_attenuation(0.0, 0.0, 0.01);
+        let near = m.lighting(
+            &Sphere::new(),
+            light,
+            Point::new(0.0, 0.0, 0.0),
+            eyev,
+            normalv,
+            false,
+        );
+        let far = m.lighting(
+            &Sphere::new(),
+            light,
+            Point::new(0.0, 0.0, 10.0),
+            eyev,
+            normalv,
+            false,
+        );
+
+        assert_eq!(near, Color::new(0.9, 0.9, 0.9));
+        assert_eq!(far, Color::new(0.225, 0.225, 0.225));
+    }
+
+    #[test]
+    fn lighting_an_emissive_surface_in_shadow() {
+        let mut m = Material::new();
+        m.emissive = Color::new(0.5, 0.0, 0.0);
+        let position = Point::new(0.0, 0.0, 0.0);
+        let eyev = Vector::new(0.0, 0.0, -1.0);
+        let normalv = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Point::new(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+        let result = m.lighting(&Sphere::new(), light, position, eyev, normalv, true);
+
+        assert_eq!(result, Color::new(0.6, 0.1, 0.1));
+    }
+
     // Chapter 11 Reflection and Refraction
     // Page 143
     #[test]
@@ -292,4 +597,126 @@ mod tests {
         assert_eq!(m.transparency, 0.0);
         assert_eq!(m.refractive_index, 1.0);
     }
+
+    #[test]
+    fn the_glass_material() {
+        let m = Material::glass();
+
+        assert_eq!(m.transparency, 1.0);
+        assert_eq!(m.refractive_index, 1.5);
+        assert_eq!(m.reflective, 0.0);
+        assert_eq!(m.color, Color::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn the_mirror_material() {
+        let m = Material::mirror();
+
+        assert_eq!(m.reflective, 1.0);
+        assert_eq!(m.ambient, 0.0);
+        assert_eq!(m.diffuse, 0.0);
+        assert_eq!(m.specular, 0.9);
+        assert_eq!(m.transparency, 0.0);
+    }
+
+    #[test]
+    fn the_matte_material() {
+        let m = Material::matte(Color::new(0.2, 0.4, 0.6));
+
+        assert_eq!(m.color, Color::new(0.2, 0.4, 0.6));
+        assert_eq!(m.diffuse, 0.9);
+        assert_eq!(m.specular, 0.0);
+        assert_eq!(m.reflective, 0.0);
+        assert_eq!(m.transparency, 0.0);
+    }
+
+    #[test]
+    fn a_diamond_material_has_the_refractive_index_of_diamond() {
+        let m = Material::new().with_refraction(Material::DIAMOND);
+
+        assert_eq!(m.refractive_index, 2.417);
+        assert_eq!(Material::new().refractive_index, Material::AIR);
+    }
+
+    #[test]
+    fn a_toon_material_shades_a_sphere_in_bands() {
+        let m = Material {
+            ambient: 0.0,
+            specular: 0.0,
+            shading: ShadingModel::Toon { bands: 2 },
+            ..Material::new()
+        };
+        let s = Sphere::new();
+        let light = PointLight::new(Point::new(0.0, 0.0, -1.0e6), Colors::WHITE);
+
+        let mut values = vec![];
+        for i in 0..20 {
+            for j in 0..40 {
+                let theta = (i as f64 + 0.5) / 20.0 * std::f64::consts::FRAC_PI_2;
+                let phi = j as f64 / 40.0 * std::f64::consts::TAU;
+                let point = Point::new(
+                    theta.sin() * phi.cos(),
+                    theta.sin() * phi.sin(),
+                    -theta.cos(),
+                );
+                let normalv = s.normal_at(point, None, None);
+                let c = m.lighting(&s, light, point, normalv, normalv, false);
+                values.push(c.red);
+            }
+        }
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        values.dedup();
+
+        assert_eq!(values, vec![0.45, 0.9]);
+    }
+
+    #[test]
+    fn lighting_a_hit_samples_an_image_at_the_texture_coordinates() {
+        let mut canvas = crate::Canvas::new(3, 3);
+        for y in 0..3 {
+            for x in 0..3 {
+                canvas.write_pixel(x, y, Color::new(x as f64, y as f64, 0.0));
+            }
+        }
+        let mut t = crate::shapes::Triangle::smooth_triangle(
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+            Vector::new(-1.0, 0.0, 0.0),
+            Vector::new(1.0, 0.0, 0.0),
+        );
+        t.uv1 = Some((0.5, 1.0));
+        t.uv2 = Some((0.0, 0.0));
+        t.uv3 = Some((1.0, 0.0));
+        let m = Material {
+            pattern: Some(Box::new(crate::patterns::ImagePattern::new(canvas))),
+            ambient: 1.0,
+            diffuse: 0.0,
+            specular: 0.0,
+            ..Material::new()
+        };
+        let r = crate::Ray::new(Point::new(-0.2, 0.3, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = t.intersect(r).unwrap();
+        let comps = xs[0].prepare_computations(r, &xs, None);
+        let light = PointLight::new(Point::new(0.0, 0.0, -10.0), Colors::WHITE);
+
+        // (0.4, 0.3) interpolated from the vertices is the pixel at x = 1 and
+        // y = 1, the planar mapping of the point is the pixel x = 2 and y = 2.
+        assert_eq!(
+            m.lighting_at(&comps, light, false, None),
+            Color::new(1.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            m.lighting(
+                &t,
+                light,
+                comps.over_point,
+                comps.eyev,
+                comps.normalv,
+                false
+            ),
+            Color::new(2.0, 2.0, 0.0)
+        );
+    }
 }
@@ -13,6 +13,10 @@ pub struct Matrix {
     inverse: [[f64; 4]; 4],
 }
 
+/// Largest pivot value considered zero when inverting a matrix with Gauss-Jordan
+/// elimination.
+const SINGULAR_PIVOT: f64 = 1e-12;
+
 /// A matrix in which all the elements of the principal diagonal are ones
 /// and all other elements are zeros. The effect of multiplying a given matrix
 /// by an identity matrix is to leave the given matrix unchanged.
@@ -78,6 +82,84 @@ impl Matrix {
         Self { data, inverse }
     }
 
+    /// Creates a Matrix from four rows. The same as `new` without having to
+    /// nest the rows in an outer array.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Matrix, IDENTITY};
+    ///
+    /// let m = Matrix::from_rows(
+    ///     [1.0, 0.0, 0.0, 0.0],
+    ///     [0.0, 1.0, 0.0, 0.0],
+    ///     [0.0, 0.0, 1.0, 0.0],
+    ///     [0.0, 0.0, 0.0, 1.0],
+    /// );
+    ///
+    /// assert_eq!(m, IDENTITY);
+    /// ```
+    pub fn from_rows(r0: [f64; 4], r1: [f64; 4], r2: [f64; 4], r3: [f64; 4]) -> Self {
+        Matrix::new([r0, r1, r2, r3])
+    }
+
+    /// Creates a scaling Matrix that scales all three axes by `s`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Matrix, Point};
+    ///
+    /// let m = Matrix::scaling_uniform(2.0);
+    ///
+    /// assert_eq!(m * Point::new(1.0, -2.0, 3.0), Point::new(2.0, -4.0, 6.0));
+    /// ```
+    pub fn scaling_uniform(s: f64) -> Self {
+        Matrix::new([
+            [s, 0.0, 0.0, 0.0],
+            [0.0, s, 0.0, 0.0],
+            [0.0, 0.0, s, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Returns a copy of row `i` of the matrix.
+    pub fn row(&self, i: usize) -> [f64; 4] {
+        self.data[i]
+    }
+
+    /// Returns a copy of column `j` of the matrix.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::IDENTITY;
+    ///
+    /// assert_eq!(IDENTITY.col(2), [0.0, 0.0, 1.0, 0.0]);
+    /// ```
+    pub fn col(&self, j: usize) -> [f64; 4] {
+        [
+            self.data[0][j],
+            self.data[1][j],
+            self.data[2][j],
+            self.data[3][j],
+        ]
+    }
+
+    /// Compare `self` with `other` element by element using the given error
+    /// value `eps` instead of [`crate::EPSILON`] used by `==`.
+    pub fn approx_eq(&self, other: &Matrix, eps: f64) -> bool {
+        for r in 0..4 {
+            for c in 0..4 {
+                if (self.data[r][c] - other.data[r][c]).abs() >= eps {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
     /// Returns a new `Matrix` where it's `data` is created from the `inverse`
     /// array of `self`. Calculating an inverse of a matrix is expensive. The
     /// `inverse` function is called multiple times for a single ray. The
@@ -151,69 +233,230 @@ impl Matrix {
     /// assert_eq!(m1.transpose(), expected);
     /// ```
     pub fn transpose(&self) -> Self {
-        let d = [
-            [
-                self.data[0][0],
-                self.data[1][0],
-                self.data[2][0],
-                self.data[3][0],
-            ],
-            [
-                self.data[0][1],
-                self.data[1][1],
-                self.data[2][1],
-                self.data[3][1],
-            ],
-            [
-                self.data[0][2],
-                self.data[1][2],
-                self.data[2][2],
-                self.data[3][2],
-            ],
-            [
-                self.data[0][3],
-                self.data[1][3],
-                self.data[2][3],
-                self.data[3][3],
-            ],
-        ];
-
-        let it = [
-            [
-                self.inverse[0][0],
-                self.inverse[1][0],
-                self.inverse[2][0],
-                self.inverse[3][0],
-            ],
-            [
-                self.inverse[0][1],
-                self.inverse[1][1],
-                self.inverse[2][1],
-                self.inverse[3][1],
-            ],
-            [
-                self.inverse[0][2],
-                self.inverse[1][2],
-                self.inverse[2][2],
-                self.inverse[3][2],
-            ],
-            [
-                self.inverse[0][3],
-                self.inverse[1][3],
-                self.inverse[2][3],
-                self.inverse[3][3],
-            ],
-        ];
+        Matrix {
+            data: Matrix::transpose_array(self.data),
+            inverse: Matrix::transpose_array(self.inverse),
+        }
+    }
 
+    /// The transpose of the inverse of `self`, used to convert normal vectors
+    /// from object space to world space. The same as `inverse().transpose()`
+    /// built straight from the retained arrays, the inverse is not calculated
+    /// again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::Transformation;
+    ///
+    /// let m = Transformation::new().scale(1.0, 0.5, 1.0).rotate_z(0.6).build();
+    ///
+    /// assert_eq!(m.inverse_transpose(), m.inverse().transpose());
+    /// ```
+    pub fn inverse_transpose(&self) -> Self {
         Matrix {
-            data: d,
-            inverse: it,
+            data: Matrix::transpose_array(self.inverse),
+            inverse: Matrix::transpose_array(self.data),
+        }
+    }
+
+    /// Split `self` into its translation, scale and rotation components,
+    /// assuming the matrix was built from those alone without any shearing.
+    /// The scale along each axis is the length of the matching column and the
+    /// rotation is made up of the columns divided by that length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Transformation, Vector, IDENTITY};
+    ///
+    /// let m = Transformation::new()
+    ///     .scale(2.0, 2.0, 2.0)
+    ///     .translate(1.0, 2.0, 3.0)
+    ///     .build();
+    /// let (translation, scale, rotation) = m.decompose();
+    ///
+    /// assert_eq!(translation, Vector::new(1.0, 2.0, 3.0));
+    /// assert_eq!(scale, Vector::new(2.0, 2.0, 2.0));
+    /// assert_eq!(rotation, IDENTITY);
+    /// ```
+    pub fn decompose(&self) -> (Vector, Vector, Matrix) {
+        let d = self.data;
+        let translation = Vector::new(d[0][3], d[1][3], d[2][3]);
+        let mut factors = [0.0; 3];
+        let mut rotation = IDENTITY.data;
+
+        for (col, factor) in factors.iter_mut().enumerate() {
+            *factor = Vector::new(d[0][col], d[1][col], d[2][col]).magnitude();
+            if *factor != 0.0 {
+                for (row, r) in rotation.iter_mut().take(3).enumerate() {
+                    r[col] = d[row][col] / *factor;
+                }
+            }
         }
+
+        let scale = Vector::new(factors[0], factors[1], factors[2]);
+        let rotation = Matrix {
+            data: rotation,
+            inverse: Matrix::transpose_array(rotation),
+        };
+
+        (translation, scale, rotation)
+    }
+
+    fn transpose_array(a: [[f64; 4]; 4]) -> [[f64; 4]; 4] {
+        [
+            [a[0][0], a[1][0], a[2][0], a[3][0]],
+            [a[0][1], a[1][1], a[2][1], a[3][1]],
+            [a[0][2], a[1][2], a[2][2], a[3][2]],
+            [a[0][3], a[1][3], a[2][3], a[3][3]],
+        ]
     }
 
     /// Test if matrix `self` can be inverted
     pub fn is_invertible(&self) -> bool {
-        !(Matrix::determinant(self.data, 4) == 0.0)
+        Matrix::gauss_jordan(self.data).is_some()
+    }
+
+    /// Calculate the inverse of `self` using Gauss-Jordan elimination with
+    /// partial pivoting instead of the cofactor expansion used by `new`.
+    /// Returns `None` if the matrix is singular and can't be inverted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::Matrix;
+    ///
+    /// let m = Matrix::new([
+    ///     [ 8.0, -5.0,  9.0,  2.0],
+    ///     [ 7.0,  5.0,  6.0,  1.0],
+    ///     [-6.0,  0.0,  9.0,  6.0],
+    ///     [-3.0,  0.0, -9.0, -4.0],
+    /// ]);
+    ///
+    /// assert_eq!(m.inverse_gauss().unwrap(), m.inverse());
+    /// ```
+    pub fn inverse_gauss(&self) -> Option<Matrix> {
+        Matrix::gauss_jordan(self.data).map(|inverse| Matrix {
+            data: inverse,
+            inverse: self.data,
+        })
+    }
+
+    /// Format only the top-left `n` rows and columns of the matrix, for a
+    /// matrix used as a 2x2 or 3x3. Each row is on its own line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is more than 4.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::Matrix;
+    ///
+    /// let m = Matrix::new([
+    ///     [1.0, 5.0, 0.0, 0.0],
+    ///     [-3.0, 2.0, 0.0, 0.0],
+    ///     [0.0, 0.0, 0.0, 0.0],
+    ///     [0.0, 0.0, 0.0, 0.0],
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     m.format_size(2),
+    ///     "   1.00000   5.00000\n  -3.00000   2.00000\n"
+    /// );
+    /// ```
+    pub fn format_size(&self, n: usize) -> String {
+        assert!(n <= 4, "a matrix has at most 4 rows and columns");
+
+        Matrix::format_rows(self.data, n)
+            .iter()
+            .map(|row| format!("{}\n", row))
+            .collect()
+    }
+
+    /// Format the matrix with the inverse stored along with it, side by side
+    /// with the rows of the matrix on the left and the rows of the inverse on
+    /// the right.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::Transformation;
+    ///
+    /// let m = Transformation::new().scale(2.0, 4.0, 1.0).build();
+    /// let text = m.debug_inverse();
+    ///
+    /// assert_eq!(text.lines().count(), 4);
+    /// let (data, inverse) = text.lines().next().unwrap().split_once('|').unwrap();
+    ///
+    /// assert!(data.trim().starts_with("2.00000"));
+    /// assert!(inverse.trim().starts_with("0.50000"));
+    /// ```
+    pub fn debug_inverse(&self) -> String {
+        Matrix::format_rows(self.data, 4)
+            .iter()
+            .zip(Matrix::format_rows(self.inverse, 4))
+            .map(|(data, inverse)| format!("{}  |{}\n", data, inverse))
+            .collect()
+    }
+
+    // Each of the first `n` rows of `a` formatted with the first `n` columns.
+    fn format_rows(a: [[f64; 4]; 4], n: usize) -> Vec<String> {
+        a.iter()
+            .take(n)
+            .map(|row| {
+                row.iter()
+                    .take(n)
+                    .map(|v| format!("{0:>10}", format!("{0:.5}", v)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Reduce `a` to the identity matrix while applying the same row operations
+    /// to an identity matrix, which becomes the inverse of `a`. The row with
+    /// the largest value in the current column is swapped into the pivot
+    /// position to keep the error down. A pivot of zero means `a` is singular.
+    #[allow(clippy::needless_range_loop)]
+    fn gauss_jordan(a: [[f64; 4]; 4]) -> Option<[[f64; 4]; 4]> {
+        let mut a = a;
+        let mut inverse = IDENTITY.data;
+
+        for col in 0..4 {
+            let mut pivot = col;
+            for row in col + 1..4 {
+                if a[row][col].abs() > a[pivot][col].abs() {
+                    pivot = row;
+                }
+            }
+
+            if a[pivot][col].abs() < SINGULAR_PIVOT {
+                return None;
+            }
+
+            a.swap(col, pivot);
+            inverse.swap(col, pivot);
+
+            let p = a[col][col];
+            for c in 0..4 {
+                a[col][c] /= p;
+                inverse[col][c] /= p;
+            }
+
+            for row in 0..4 {
+                if row != col {
+                    let factor = a[row][col];
+                    for c in 0..4 {
+                        a[row][c] -= factor * a[col][c];
+                        inverse[row][c] -= factor * inverse[col][c];
+                    }
+                }
+            }
+        }
+
+        Some(inverse)
     }
 
     fn determinant(a: [[f64; 4]; 4], s: usize) -> f64 {
@@ -334,34 +577,15 @@ impl PartialEq for Matrix {
 
 impl fmt::Display for Matrix {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{0:>10}", format!("{0:.5}", self.data[0][0]))?;
-        write!(f, "{0:>10}", format!("{0:.5}", self.data[0][1]))?;
-        write!(f, "{0:>10}", format!("{0:.5}", self.data[0][2]))?;
-        writeln!(f, "{0:>10}", format!("{0:.5}", self.data[0][3]))?;
-
-        write!(f, "{0:>10}", format!("{0:.5}", self.data[1][0]))?;
-        write!(f, "{0:>10}", format!("{0:.5}", self.data[1][1]))?;
-        write!(f, "{0:>10}", format!("{0:.5}", self.data[1][2]))?;
-        writeln!(f, "{0:>10}", format!("{0:.5}", self.data[1][3]))?;
-
-        write!(f, "{0:>10}", format!("{0:.5}", self.data[2][0]))?;
-        write!(f, "{0:>10}", format!("{0:.5}", self.data[2][1]))?;
-        write!(f, "{0:>10}", format!("{0:.5}", self.data[2][2]))?;
-        writeln!(f, "{0:>10}", format!("{0:.5}", self.data[2][3]))?;
-
-        write!(f, "{0:>10}", format!("{0:.5}", self.data[3][0]))?;
-        write!(f, "{0:>10}", format!("{0:.5}", self.data[3][1]))?;
-        write!(f, "{0:>10}", format!("{0:.5}", self.data[3][2]))?;
-        writeln!(f, "{0:>10}", format!("{0:.5}", self.data[3][3]))?;
-
-        Ok(())
+        write!(f, "{}", self.format_size(4))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{Matrix, IDENTITY};
-    use crate::{float_eq, Point};
+    use crate::{float_eq, Point, Transformation, Vector};
+    use std::f64::consts::PI;
 
     // Chapter 3 Matrices
     // Page 26
@@ -808,4 +1032,145 @@ mod tests {
         
         assert_eq!(c * b.inverse(), a);
     }
+
+    #[test]
+    fn extracting_the_columns_of_the_identity_matrix() {
+        assert_eq!(IDENTITY.col(0), [1.0, 0.0, 0.0, 0.0]);
+        assert_eq!(IDENTITY.col(1), [0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(IDENTITY.col(2), [0.0, 0.0, 1.0, 0.0]);
+        assert_eq!(IDENTITY.col(3), [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(IDENTITY.row(3), IDENTITY.col(3));
+    }
+
+    #[test]
+    fn a_uniform_scaling_matrix_applied_to_a_point() {
+        let m = Matrix::scaling_uniform(2.0);
+        let p = Point::new(-4.0, 6.0, 8.0);
+
+        assert_eq!(m * p, Point::new(-8.0, 12.0, 16.0));
+        assert_eq!(m.inverse() * p, Point::new(-2.0, 3.0, 4.0));
+    }
+
+    #[test]
+    fn comparing_matrices_with_a_custom_tolerance() {
+        let a = Matrix::scaling_uniform(2.0);
+        let b = Matrix::scaling_uniform(2.001);
+
+        assert!(a.approx_eq(&b, 0.01));
+        assert!(!a.approx_eq(&b, 0.0001));
+    }
+
+    #[test]
+    fn the_inverse_transpose_matches_transposing_the_inverse() {
+        let m = Matrix::new([
+            [8.0, -5.0, 9.0, 2.0],
+            [7.0, 5.0, 6.0, 1.0],
+            [-6.0, 0.0, 9.0, 6.0],
+            [-3.0, 0.0, -9.0, -4.0],
+        ]);
+
+        assert_eq!(m.inverse_transpose(), m.inverse().transpose());
+        assert_eq!(m.inverse_transpose().inverse(), m.transpose());
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn gauss_jordan_inverse_agrees_with_the_cofactor_inverse() {
+        let fixtures = [
+            [
+                [-5.0,  2.0,  6.0, -8.0],
+                [ 1.0, -5.0,  1.0,  8.0],
+                [ 7.0,  7.0, -6.0, -7.0],
+                [ 1.0, -3.0,  7.0,  4.0],
+            ],
+            [
+                [ 8.0, -5.0,  9.0,  2.0],
+                [ 7.0,  5.0,  6.0,  1.0],
+                [-6.0,  0.0,  9.0,  6.0],
+                [-3.0,  0.0, -9.0, -4.0],
+            ],
+            [
+                [ 9.0,  3.0,  0.0,  9.0],
+                [-5.0, -2.0, -6.0, -3.0],
+                [-4.0,  9.0,  6.0,  4.0],
+                [-7.0,  6.0,  6.0,  2.0],
+            ],
+        ];
+
+        for f in fixtures {
+            let m = Matrix::new(f);
+            let gauss = m.inverse_gauss().expect("Matrix should be invertible!");
+
+            for row in 0..4 {
+                for col in 0..4 {
+                    assert!(float_eq(gauss[row][col], m.inverse()[row][col]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn gauss_jordan_inverse_of_a_singular_matrix() {
+        let a = Matrix::new([
+            [-4.0,  2.0, -2.0, -3.0],
+            [ 9.0,  6.0,  2.0,  6.0],
+            [ 0.0, -5.0,  1.0, -5.0],
+            [ 0.0,  0.0,  0.0,  0.0],
+        ]);
+
+        assert!(a.inverse_gauss().is_none());
+    }
+
+    #[test]
+    fn decomposing_a_translation_and_scaling() {
+        let m = Transformation::new()
+            .scale(2.0, 2.0, 2.0)
+            .translate(1.0, 2.0, 3.0)
+            .build();
+        let (translation, scale, rotation) = m.decompose();
+
+        assert!(float_eq(translation.x, 1.0));
+        assert!(float_eq(translation.y, 2.0));
+        assert!(float_eq(translation.z, 3.0));
+        assert!(float_eq(scale.x, 2.0));
+        assert!(float_eq(scale.y, 2.0));
+        assert!(float_eq(scale.z, 2.0));
+        assert_eq!(rotation, IDENTITY);
+    }
+
+    #[test]
+    fn decomposing_a_rotation() {
+        let rotate = Transformation::new().rotate_y(PI / 3.0).build();
+        let m = Transformation::new()
+            .scale(1.0, 2.0, 3.0)
+            .rotate_y(PI / 3.0)
+            .translate(-4.0, 0.5, 2.0)
+            .build();
+        let (translation, scale, rotation) = m.decompose();
+
+        assert_eq!(translation, Vector::new(-4.0, 0.5, 2.0));
+        assert_eq!(scale, Vector::new(1.0, 2.0, 3.0));
+        assert_eq!(rotation, rotate);
+        assert_eq!(rotation.inverse(), rotate.inverse());
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn formatting_a_2x2_matrix_prints_two_rows_and_columns() {
+        let a = Matrix::new([
+            [ 1.0, 5.0, 9.0, 9.0],
+            [-3.0, 2.0, 9.0, 9.0],
+            [ 9.0, 9.0, 9.0, 9.0],
+            [ 9.0, 9.0, 9.0, 9.0],
+        ]);
+        let text = a.format_size(2);
+        let rows: Vec<Vec<&str>> = text
+            .lines()
+            .map(|row| row.split_whitespace().collect())
+            .collect();
+
+        assert_eq!(rows, vec![vec!["1.00000", "5.00000"], vec!["-3.00000", "2.00000"]]);
+        assert_eq!(a.format_size(4), a.to_string());
+    }
 }
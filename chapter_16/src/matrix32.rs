@@ -0,0 +1,189 @@
+use crate::{Matrix, Point};
+use std::ops::{Index, Mul};
+
+/// A 4x4 [`Matrix`] stored as [`f32`] numbers. Trades precision for speed
+/// and is meant for quick low quality previews, points transformed with it
+/// only agree with the [`f64`] matrix to about three decimal places.
+#[derive(Debug, Clone, Copy)]
+pub struct Matrix32 {
+    data: [[f32; 4]; 4],
+    inverse: [[f32; 4]; 4],
+}
+
+impl Matrix32 {
+    /// Creates a Matrix32 with the provided 4x4 array of [`f32`] numbers. The
+    /// inverse is calculated once with [`f64`] precision and stored as
+    /// [`f32`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::Matrix32;
+    ///
+    /// let m = Matrix32::new([
+    ///     [1.0, 0.0, 0.0, 5.0],
+    ///     [0.0, 1.0, 0.0, -3.0],
+    ///     [0.0, 0.0, 1.0, 2.0],
+    ///     [0.0, 0.0, 0.0, 1.0],
+    /// ]);
+    ///
+    /// assert_eq!(m[0][3], 5.0);
+    /// assert_eq!(m.inverse()[0][3], -5.0);
+    /// ```
+    pub fn new(data: [[f32; 4]; 4]) -> Self {
+        Matrix32::from(Matrix::new(Matrix32::widen(data)))
+    }
+
+    /// The inverse of the matrix, no calculation is done the inverse is
+    /// retained when the matrix is created.
+    pub fn inverse(&self) -> Matrix32 {
+        Matrix32 {
+            data: self.inverse,
+            inverse: self.data,
+        }
+    }
+
+    /// Switch the rows and column indices of a matrix, it flips a matrix over
+    /// its diagonal.
+    pub fn transpose(&self) -> Self {
+        Matrix32 {
+            data: Matrix32::transpose_array(self.data),
+            inverse: Matrix32::transpose_array(self.inverse),
+        }
+    }
+
+    fn transpose_array(a: [[f32; 4]; 4]) -> [[f32; 4]; 4] {
+        let mut results = [[0.0; 4]; 4];
+        for (row, values) in a.iter().enumerate() {
+            for (col, value) in values.iter().enumerate() {
+                results[col][row] = *value;
+            }
+        }
+        results
+    }
+
+    fn narrow(a: [[f64; 4]; 4]) -> [[f32; 4]; 4] {
+        a.map(|row| row.map(|v| v as f32))
+    }
+
+    fn widen(a: [[f32; 4]; 4]) -> [[f64; 4]; 4] {
+        a.map(|row| row.map(f64::from))
+    }
+}
+
+impl From<Matrix> for Matrix32 {
+    fn from(m: Matrix) -> Self {
+        let rows = |m: &Matrix| [m.row(0), m.row(1), m.row(2), m.row(3)];
+        Matrix32 {
+            data: Matrix32::narrow(rows(&m)),
+            inverse: Matrix32::narrow(rows(&m.inverse())),
+        }
+    }
+}
+
+impl From<Matrix32> for Matrix {
+    fn from(m: Matrix32) -> Self {
+        Matrix::new(Matrix32::widen(m.data))
+    }
+}
+
+impl Mul<Point> for Matrix32 {
+    type Output = Point;
+
+    fn mul(self, rhs: Point) -> Point {
+        let (x, y, z) = (rhs.x as f32, rhs.y as f32, rhs.z as f32);
+        let m = &self.data;
+
+        Point::new(
+            (m[0][0] * x + m[0][1] * y + m[0][2] * z + m[0][3]) as f64,
+            (m[1][0] * x + m[1][1] * y + m[1][2] * z + m[1][3]) as f64,
+            (m[2][0] * x + m[2][1] * y + m[2][2] * z + m[2][3]) as f64,
+        )
+    }
+}
+
+impl Index<usize> for Matrix32 {
+    type Output = [f32; 4];
+
+    fn index(&self, i: usize) -> &Self::Output {
+        &self.data[i]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Transformation;
+    use std::f64::consts::PI;
+
+    fn close(a: Point, b: Point) -> bool {
+        (a.x - b.x).abs() < 0.001 && (a.y - b.y).abs() < 0.001 && (a.z - b.z).abs() < 0.001
+    }
+
+    #[test]
+    fn transforming_a_point_is_close_to_the_f64_result() {
+        let m = Transformation::new()
+            .rotate_x(PI / 3.0)
+            .rotate_y(0.7)
+            .scale(2.5, 0.5, 1.5)
+            .translate(10.0, -4.0, 3.0)
+            .build();
+        let p = Point::new(-3.3, 7.1, 0.25);
+
+        assert!(close(Matrix32::from(m) * p, m * p));
+    }
+
+    #[test]
+    fn inverse_transforms_a_point_back() {
+        let m = Transformation::new()
+            .rotate_z(0.4)
+            .scale(3.0, 2.0, 0.5)
+            .translate(1.0, 2.0, 3.0)
+            .build();
+        let m32 = Matrix32::from(m);
+        let p = Point::new(1.5, -2.0, 4.0);
+
+        assert!(close(m32.inverse() * (m32 * p), p));
+        assert!(close(m32.inverse() * p, m.inverse() * p));
+    }
+
+    #[test]
+    fn new_calculates_the_inverse() {
+        let m = Matrix32::new([
+            [2.0, 0.0, 0.0, 1.0],
+            [0.0, 4.0, 0.0, 0.0],
+            [0.0, 0.0, 0.5, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+
+        assert!(close(
+            m.inverse() * Point::new(5.0, 4.0, 1.0),
+            Point::new(2.0, 1.0, 2.0)
+        ));
+    }
+
+    #[test]
+    fn transpose_flips_the_matrix() {
+        let m = Matrix32::new([
+            [0.0, 9.0, 3.0, 0.0],
+            [9.0, 8.0, 0.0, 8.0],
+            [1.0, 8.0, 5.0, 3.0],
+            [0.0, 0.0, 5.0, 8.0],
+        ]);
+        let t = m.transpose();
+
+        assert_eq!(t[0], [0.0, 9.0, 1.0, 0.0]);
+        assert_eq!(t[3], [0.0, 8.0, 3.0, 8.0]);
+    }
+
+    #[test]
+    fn converting_round_trip() {
+        let m = Transformation::new()
+            .rotate_y(0.3)
+            .translate(2.0, 0.0, -1.0)
+            .build();
+        let back: Matrix = Matrix32::from(m).into();
+
+        assert!(back.approx_eq(&m, 0.001));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    shapes::{Group, Triangle},
+    shapes::{Group, Polygon, Triangle},
     Point, Vector,
 };
 
@@ -7,7 +7,20 @@ struct ObjParser {
     ignored_lines: usize,
     vertices: Vec<Point>,
     normals: Vec<Vector>,
+    textures: Vec<(f64, f64)>,
     default_group: Group,
+    // Faces with more than three vertices become a `Polygon` instead of a
+    // fan of triangles.
+    polygons: bool,
+}
+
+impl ObjParser {
+    /// Look up a texture coordinate by its one based `vt` index from a face.
+    fn texture(&self, index: Option<usize>) -> Option<(f64, f64)> {
+        index
+            .and_then(|i| i.checked_sub(1))
+            .and_then(|i| self.textures.get(i).copied())
+    }
 }
 
 /// Build objects by parsing a Wavefront OBJ file
@@ -22,16 +35,38 @@ impl ObjFile {
     /// Parse a Wavefront OBJ string returning a [`Group`] object with all of the
     /// triangles and polygons in the `buffer`.
     pub fn parse(buffer: &str) -> Group {
-        let parser = ObjFile::parse_obj_file(buffer);
+        let parser = ObjFile::parse_obj_file(buffer, false);
         parser.default_group
     }
 
-    fn parse_obj_file(buffer: &str) -> ObjParser {
+    /// Parse a Wavefront OBJ string the same as `parse`, except a face with
+    /// more than three vertices becomes a single [`Polygon`] instead of a fan
+    /// of triangles so concave faces keep their shape. The vertex normals and
+    /// texture coordinates of those faces are not used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::ObjFile;
+    ///
+    /// let file = "v 0 4 0\nv 0 0 0\nv 4 0 0\nv 4 4 0\nv 2 1 0\nf 1 2 3 4 5\n";
+    ///
+    /// assert_eq!(ObjFile::parse(file).objects().len(), 3);
+    /// assert_eq!(ObjFile::parse_with_polygons(file).objects().len(), 1);
+    /// ```
+    pub fn parse_with_polygons(buffer: &str) -> Group {
+        let parser = ObjFile::parse_obj_file(buffer, true);
+        parser.default_group
+    }
+
+    fn parse_obj_file(buffer: &str, polygons: bool) -> ObjParser {
         let mut parser = ObjParser {
             ignored_lines: 0,
             vertices: Vec::new(),
             normals: Vec::new(),
+            textures: Vec::new(),
             default_group: Group::new(),
+            polygons,
         };
 
         let mut group = GroupType::Parent;
@@ -52,22 +87,23 @@ impl ObjFile {
                         let z: f64 = line_iter.next().unwrap().parse().unwrap();
                         parser.normals.push(Vector::new(x, y, z));
                     }
+                    "vt" => {
+                        let u: f64 = line_iter.next().unwrap().parse().unwrap();
+                        let v: f64 = line_iter.next().unwrap().parse().unwrap();
+                        parser.textures.push((u, v));
+                    }
                     "f" => {
                         ObjFile::parse_faces(&mut parser, &mut line_iter, &mut group);
                     }
-                    "g" => match group {
-                        GroupType::Parent => {
-                            let mut child_group = Group::new();
-                            child_group.inherit_material = true;
-                            group = GroupType::Child(Box::new(child_group));
-                        }
-                        GroupType::Child(g) => {
+                    "g" => {
+                        if let GroupType::Child(g) = group {
                             parser.default_group.add_object(g);
-                            let mut child_group = Group::new();
-                            child_group.inherit_material = true;
-                            group = GroupType::Child(Box::new(child_group));
                         }
-                    },
+                        let mut child_group = Group::new();
+                        child_group.inherit_material = true;
+                        child_group.name = line_iter.next().map(|name| name.to_string());
+                        group = GroupType::Child(Box::new(child_group));
+                    }
                     _ => {
                         parser.ignored_lines += 1;
                     }
@@ -87,45 +123,47 @@ impl ObjFile {
         line_iter: &mut std::str::SplitWhitespace,
         group: &mut GroupType,
     ) {
-        let mut vg: Vec<(i32, i32)> = Vec::new();
-        let mut has_vn = false;
+        let mut vg: Vec<(usize, Option<usize>, Option<usize>)> = Vec::new();
         for v in line_iter.by_ref() {
-            if v.contains('/') {
-                has_vn = true;
-                let v_vt_vn: Vec<&str> = v.split('/').collect();
-                let vi: i32 = v_vt_vn[0].parse().unwrap();
-                let vni: i32 = v_vt_vn[2].parse().unwrap();
-                vg.push((vi - 1, vni - 1));
-            } else {
-                let vi: i32 = v.parse().unwrap();
-                vg.push((vi - 1, 0));
+            let mut v_vt_vn = v.split('/');
+            let vi: usize = v_vt_vn.next().unwrap().parse().unwrap();
+            let vti: Option<usize> = v_vt_vn.next().and_then(|vt| vt.parse().ok());
+            let vni: Option<usize> = v_vt_vn.next().and_then(|vn| vn.parse().ok());
+            vg.push((vi - 1, vti, vni.map(|vni| vni - 1)));
+        }
+        if parser.polygons && vg.len() > 3 {
+            let vertices = vg.iter().map(|v| parser.vertices[v.0]).collect();
+            let polygon = Box::new(Polygon::new(vertices));
+            match group {
+                GroupType::Parent => parser.default_group.add_object(polygon),
+                GroupType::Child(g) => g.add_object(polygon),
             }
+            return;
         }
+
+        let has_vn = vg.iter().all(|v| v.2.is_some());
         for index in 1..vg.len() - 1 {
-            if has_vn {
-                let p1 = parser.vertices[vg[0].0 as usize];
-                let p2 = parser.vertices[vg[index].0 as usize];
-                let p3 = parser.vertices[vg[index + 1].0 as usize];
-
-                let n1 = parser.normals[vg[0].1 as usize];
-                let n2 = parser.normals[vg[index].1 as usize];
-                let n3 = parser.normals[vg[index + 1].1 as usize];
-
-                let tri = Triangle::smooth_triangle(p1, p2, p3, n1, n2, n3);
-                match group {
-                    GroupType::Parent => parser.default_group.add_object(Box::new(tri)),
-                    GroupType::Child(g) => g.add_object(Box::new(tri)),
-                }
+            let p1 = parser.vertices[vg[0].0];
+            let p2 = parser.vertices[vg[index].0];
+            let p3 = parser.vertices[vg[index + 1].0];
+
+            let mut tri = if has_vn {
+                let n1 = parser.normals[vg[0].2.unwrap()];
+                let n2 = parser.normals[vg[index].2.unwrap()];
+                let n3 = parser.normals[vg[index + 1].2.unwrap()];
+
+                Triangle::smooth_triangle(p1, p2, p3, n1, n2, n3)
             } else {
-                let p1 = parser.vertices[vg[0].0 as usize];
-                let p2 = parser.vertices[vg[index].0 as usize];
-                let p3 = parser.vertices[vg[index + 1].0 as usize];
-
-                let tri = Triangle::new(p1, p2, p3);
-                match group {
-                    GroupType::Parent => parser.default_group.add_object(Box::new(tri)),
-                    GroupType::Child(g) => g.add_object(Box::new(tri)),
-                }
+                Triangle::new(p1, p2, p3)
+            };
+
+            tri.uv1 = parser.texture(vg[0].1);
+            tri.uv2 = parser.texture(vg[index].1);
+            tri.uv3 = parser.texture(vg[index + 1].1);
+
+            match group {
+                GroupType::Parent => parser.default_group.add_object(Box::new(tri)),
+                GroupType::Child(g) => g.add_object(Box::new(tri)),
             }
         }
     }
@@ -145,7 +183,7 @@ who traveled much faster than light.
 She set out one day
 in a relative way,
 and came back the previous night.";
-        let parser = ObjFile::parse_obj_file(gibberish);
+        let parser = ObjFile::parse_obj_file(gibberish, false);
 
         assert_eq!(parser.ignored_lines, 5);
     }
@@ -158,7 +196,7 @@ and came back the previous night.";
 v -1.000000 0.50000 0.0000
 v 1 0 0
 v 1 1 0";
-        let parser = ObjFile::parse_obj_file(file);
+        let parser = ObjFile::parse_obj_file(file, false);
 
         assert_eq!(parser.ignored_lines, 0);
         assert_eq!(parser.vertices.len(), 4);
@@ -180,7 +218,7 @@ v 1 1 0
 
 f 1 2 3
 f 1 3 4";
-        let parser = ObjFile::parse_obj_file(file);
+        let parser = ObjFile::parse_obj_file(file, false);
         let g = &parser.default_group;
         let t1 = g.get_object(0).unwrap();
         let t1 = t1.as_any().unwrap().downcast_ref::<Triangle>().unwrap();
@@ -207,7 +245,7 @@ v 1 1 0
 v 0 2 0
 
 f 1 2 3 4 5";
-        let parser = ObjFile::parse_obj_file(file);
+        let parser = ObjFile::parse_obj_file(file, false);
         let g = &parser.default_group;
         let t1 = g.get_object(0).unwrap();
         let t1 = t1.as_any().unwrap().downcast_ref::<Triangle>().unwrap();
@@ -227,6 +265,28 @@ f 1 2 3 4 5";
         assert_eq!(t3.p3, parser.vertices[4]);
     }
 
+    #[test]
+    fn polygons_are_kept_whole_when_asked() {
+        let file = "
+v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+v 0 2 0
+
+f 1 2 3
+f 1 2 3 4 5";
+        let parser = ObjFile::parse_obj_file(file, true);
+        let g = &parser.default_group;
+        let t = g.get_object(0).unwrap();
+        let p = g.get_object(1).unwrap();
+        let p = p.as_any().unwrap().downcast_ref::<Polygon>().unwrap();
+
+        assert_eq!(g.objects().len(), 2);
+        assert!(t.as_any().unwrap().downcast_ref::<Triangle>().is_some());
+        assert_eq!(p.vertices(), &parser.vertices[..]);
+    }
+
     // Chapter 15 Triangles
     // Page 215
     #[test]
@@ -241,7 +301,7 @@ g FirstGroup
 f 1 2 3
 g SecondGroup
 f 1 3 4";
-        let parser = ObjFile::parse_obj_file(file);
+        let parser = ObjFile::parse_obj_file(file, false);
         let g = &parser.default_group;
 
         let g1 = g.get_object(0).unwrap();
@@ -264,6 +324,34 @@ f 1 3 4";
         assert_eq!(t2.p3, parser.vertices[3]);
     }
 
+    #[test]
+    fn finding_a_named_group() {
+        let file = "
+v -1 1 0
+v -1 0 0
+v 1 0 0
+v 1 1 0
+
+g FirstGroup
+f 1 2 3
+g SecondGroup
+f 1 3 4";
+        let parser = ObjFile::parse_obj_file(file, false);
+        let g = &parser.default_group;
+
+        let g2 = g.get_object_by_name("SecondGroup").unwrap();
+        let g2 = g2.as_any().unwrap().downcast_ref::<Group>().unwrap();
+        let t2 = g2.get_object(0).unwrap();
+        let t2 = t2.as_any().unwrap().downcast_ref::<Triangle>().unwrap();
+
+        assert_eq!(g2.name.as_deref(), Some("SecondGroup"));
+        assert_eq!(g2.objects().len(), 1);
+        assert_eq!(t2.p1, parser.vertices[0]);
+        assert_eq!(t2.p2, parser.vertices[2]);
+        assert_eq!(t2.p3, parser.vertices[3]);
+        assert!(g.get_object_by_name("ThirdGroup").is_none());
+    }
+
     // Chapter 15 Triangles
     // Page 223 & 224
     #[test]
@@ -271,7 +359,7 @@ f 1 3 4";
         let file = "vn 0 0 1
 vn 0.707 0 -0.707
 vn 1 2 3";
-        let parser = ObjFile::parse_obj_file(file);
+        let parser = ObjFile::parse_obj_file(file, false);
 
         assert_eq!(parser.normals[0], Vector::new(0.0, 0.0, 1.0));
         assert_eq!(parser.normals[1], Vector::new(0.707, 0.0, -0.707));
@@ -294,7 +382,7 @@ vn 0 1 0
 f 1//3 2//1 3//2
 f 1/0/3 2/102/1 3/14/2
 ";
-        let parser = ObjFile::parse_obj_file(file);
+        let parser = ObjFile::parse_obj_file(file, false);
         let g = &parser.default_group;
         let t1 = g.get_object(0).unwrap();
         let t1 = t1.as_any().unwrap().downcast_ref::<Triangle>().unwrap();
@@ -315,4 +403,45 @@ f 1/0/3 2/102/1 3/14/2
         assert_eq!(t2.n2.unwrap(), t1.n2.unwrap());
         assert_eq!(t2.n3.unwrap(), t1.n3.unwrap());
     }
+
+    #[test]
+    fn texture_coordinate_records() {
+        let file = "vt 0 0
+vt 0.5 1
+vt 1 0.25";
+        let parser = ObjFile::parse_obj_file(file, false);
+
+        assert_eq!(parser.ignored_lines, 0);
+        assert_eq!(parser.textures.len(), 3);
+        assert_eq!(parser.textures[0], (0.0, 0.0));
+        assert_eq!(parser.textures[1], (0.5, 1.0));
+        assert_eq!(parser.textures[2], (1.0, 0.25));
+    }
+
+    #[test]
+    fn faces_with_texture_coordinates() {
+        let file = "
+v 0 1 0
+v -1 0 0
+v 1 0 0
+
+vt 0.5 1
+vt 0 0
+vt 1 0
+
+f 1/1 2/2 3/3
+";
+        let parser = ObjFile::parse_obj_file(file, false);
+        let g = &parser.default_group;
+        let t = g.get_object(0).unwrap();
+        let t = t.as_any().unwrap().downcast_ref::<Triangle>().unwrap();
+
+        assert_eq!(t.p1, parser.vertices[0]);
+        assert_eq!(t.p2, parser.vertices[1]);
+        assert_eq!(t.p3, parser.vertices[2]);
+        assert!(t.n1.is_none());
+        assert_eq!(t.uv1.unwrap(), (0.5, 1.0));
+        assert_eq!(t.uv2.unwrap(), (0.0, 0.0));
+        assert_eq!(t.uv3.unwrap(), (1.0, 0.0));
+    }
 }
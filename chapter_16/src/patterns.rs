@@ -3,15 +3,23 @@
 //! function that accepts a point in space and returns a color.
 mod checkers;
 mod gradient;
+mod image;
 mod pattern;
 mod ring;
+mod scalar_checkers;
+mod scalar_pattern;
 mod stripe;
 mod test_pattern;
+mod uv_checkers;
 
 pub use checkers::Checkers;
 pub use gradient::Gradient;
+pub use image::ImagePattern;
 pub use pattern::Pattern;
 pub use ring::Ring;
+pub use scalar_checkers::ScalarCheckers;
+pub use scalar_pattern::ScalarPattern;
 pub use stripe::Stripe;
 #[cfg(test)]
 pub use test_pattern::TestPattern;
+pub use uv_checkers::UvCheckers;
@@ -1,4 +1,5 @@
 use super::Pattern;
+use crate::id::new_id;
 use crate::{Color, Matrix, Point, IDENTITY};
 use uuid::Uuid;
 
@@ -17,7 +18,7 @@ impl Checkers {
     /// Create a new checkers pattern using the [`Color`] `a` and `b`.
     pub fn new(a: Color, b: Color) -> Checkers {
         Checkers {
-            id: Uuid::new_v4(),
+            id: new_id(),
             a,
             b,
             transform: IDENTITY,
@@ -30,6 +31,10 @@ impl Pattern for Checkers {
         self.id
     }
 
+    fn box_clone(&self) -> Box<dyn Pattern> {
+        Box::new(*self)
+    }
+
     fn transform(&self) -> Matrix {
         self.transform
     }
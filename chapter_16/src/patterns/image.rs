@@ -0,0 +1,184 @@
+use super::Pattern;
+use crate::id::new_id;
+use crate::{shapes::Shape, Canvas, Color, Intersection, Matrix, Point, World, IDENTITY};
+use uuid::Uuid;
+
+/// Maps an image, a [`Canvas`], onto a shape using the `u` and `v` texture
+/// coordinates of the shape. `u` runs from the left to the right of the image
+/// and `v` from the bottom to the top.
+#[derive(Debug, Clone)]
+pub struct ImagePattern {
+    id: Uuid,
+    /// The image mapped onto the shape.
+    pub canvas: Canvas,
+    /// The transformation of the pattern.
+    pub transform: Matrix,
+}
+
+impl ImagePattern {
+    /// Create a new pattern from the image in `canvas`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{patterns::ImagePattern, Canvas, Colors};
+    ///
+    /// let mut canvas = Canvas::new(2, 2);
+    /// canvas.write_pixel(0, 0, Colors::WHITE);
+    /// let pattern = ImagePattern::new(canvas);
+    ///
+    /// assert_eq!(pattern.uv_pattern_at(0.0, 1.0), Colors::WHITE);
+    /// assert_eq!(pattern.uv_pattern_at(1.0, 0.0), Colors::BLACK);
+    /// ```
+    pub fn new(canvas: Canvas) -> ImagePattern {
+        ImagePattern {
+            id: new_id(),
+            canvas,
+            transform: IDENTITY,
+        }
+    }
+
+    /// The color of the pixel nearest to the texture coordinate `u` and `v`.
+    pub fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        self.canvas.pixel_at_uv(u, v)
+    }
+}
+
+impl Pattern for ImagePattern {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn box_clone(&self) -> Box<dyn Pattern> {
+        Box::new(self.clone())
+    }
+
+    fn transform(&self) -> Matrix {
+        self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+
+    /// Planar mapping of the `point`, the image repeats every unit along the
+    /// `x` and `z` axes.
+    fn pattern_at(&self, point: Point) -> Color {
+        self.uv_pattern_at(point.x.rem_euclid(1.0), point.z.rem_euclid(1.0))
+    }
+
+    /// Map the point onto the image with the texture coordinates of the
+    /// `object` at the `hit`.
+    fn pattern_at_hit(
+        &self,
+        object: &dyn Shape,
+        world_point: Point,
+        hit: Option<&Intersection>,
+        w: Option<&World>,
+    ) -> Color {
+        let (u, v) = self.uv_at_hit(object, world_point, hit, w);
+        self.uv_pattern_at(u, v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{float_eq, shapes::Sphere, shapes::Triangle, Ray, Vector};
+
+    // Bonus Chapter Texture Mapping
+    #[test]
+    fn checker_pattern_in_2d_from_an_image() {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.write_pixel(0, 0, Color::new(1.0, 1.0, 1.0));
+        canvas.write_pixel(1, 1, Color::new(1.0, 1.0, 1.0));
+        let pattern = ImagePattern::new(canvas);
+        let data = vec![
+            (0.0, 0.0, Color::new(0.0, 0.0, 0.0)),
+            (0.4, 0.4, Color::new(0.0, 0.0, 0.0)),
+            (0.6, 0.4, Color::new(1.0, 1.0, 1.0)),
+            (0.4, 0.6, Color::new(1.0, 1.0, 1.0)),
+            (1.0, 1.0, Color::new(0.0, 0.0, 0.0)),
+        ];
+
+        for (u, v, expected) in data {
+            assert_eq!(pattern.uv_pattern_at(u, v), expected);
+        }
+    }
+
+    #[test]
+    fn an_image_on_a_smooth_triangle_is_sampled_at_the_interpolated_uv() {
+        let mut canvas = Canvas::new(3, 3);
+        for y in 0..3 {
+            for x in 0..3 {
+                canvas.write_pixel(x, y, Color::new(x as f64, y as f64, 0.0));
+            }
+        }
+        let pattern = ImagePattern::new(canvas);
+        let mut t = Triangle::smooth_triangle(
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Vector::new(0.0, 1.0, 0.0),
+            Vector::new(-1.0, 0.0, 0.0),
+            Vector::new(1.0, 0.0, 0.0),
+        );
+        t.uv1 = Some((0.5, 1.0));
+        t.uv2 = Some((0.0, 0.0));
+        t.uv3 = Some((1.0, 0.0));
+        let r = Ray::new(Point::new(-0.2, 0.3, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = t.intersect(r).unwrap();
+        let point = r.position(xs[0].t);
+
+        let (u, v) = t.uv_at_hit(point, Some(&xs[0]));
+
+        // (0.4, 0.3) interpolated from the vertices is the pixel at x = 1 and
+        // y = 1, the planar mapping of the point is the pixel x = 2 and y = 2.
+        assert!(float_eq(u, 0.4));
+        assert!(float_eq(v, 0.3));
+        assert_eq!(
+            pattern.pattern_at_hit(&t, point, Some(&xs[0]), None),
+            Color::new(1.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            pattern.pattern_at_hit(&t, point, None, None),
+            Color::new(2.0, 2.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn an_image_on_a_sphere_uses_the_spherical_mapping() {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.write_pixel(1, 0, Color::new(1.0, 1.0, 1.0));
+        let pattern = ImagePattern::new(canvas);
+        let s = Sphere::new();
+
+        assert_eq!(
+            pattern.pattern_at_hit(&s, Point::new(0.0, 1.0, 0.0), None, None),
+            Color::new(1.0, 1.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn an_image_on_a_triangle_with_texture_coordinates_past_one_is_clamped() {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.write_pixel(1, 0, Color::new(1.0, 1.0, 1.0));
+        let pattern = ImagePattern::new(canvas);
+        let mut t = Triangle::new(
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+        );
+        t.uv1 = Some((2.0, 2.0));
+        t.uv2 = Some((1.5, 1.5));
+        t.uv3 = Some((3.0, 1.5));
+        let r = Ray::new(Point::new(0.0, 0.5, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = t.intersect(r).unwrap();
+        let point = r.position(xs[0].t);
+
+        assert_eq!(
+            pattern.pattern_at_hit(&t, point, Some(&xs[0]), None),
+            Color::new(1.0, 1.0, 1.0)
+        );
+    }
+}
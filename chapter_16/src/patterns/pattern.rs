@@ -1,13 +1,17 @@
 #[allow(unused_imports)]
 use crate::Transformation;
-use crate::{shapes::Shape, Color, Matrix, Point};
+use crate::{shapes::Shape, Color, Intersection, Matrix, Point, Vector, World};
 use std::fmt;
 use uuid::Uuid;
 
-pub trait Pattern: fmt::Debug {
+pub trait Pattern: fmt::Debug + Send + Sync {
     /// Get the unique identifier for a pattern.
     fn id(&self) -> Uuid;
 
+    /// Copy the pattern into a new [`Box`], used to clone a [`crate::Material`]
+    /// with a pattern. The copy has the same `id` as `self`.
+    fn box_clone(&self) -> Box<dyn Pattern>;
+
     /// Test if `other` is equal to `self` by comparing their `id`'s.
     fn pattern_eq(&self, other: &dyn Pattern) -> bool {
         self.id() == other.id()
@@ -45,6 +49,11 @@ pub trait Pattern: fmt::Debug {
     /// 3. Get the color of the pattern by calling `stripe_at` with the
     /// point on the pattern.
     ///
+    /// Only the transformation of the `object` itself is used, the
+    /// transformations of any [`Group`](crate::shapes::Group) the object is in
+    /// are ignored. Use `pattern_at_hit` with the world for an object inside of
+    /// a group.
+    ///
     /// # Example
     ///
     /// ```
@@ -61,9 +70,114 @@ pub trait Pattern: fmt::Debug {
     /// assert_eq!(c, Colors::WHITE);
     /// ```
     fn pattern_at_shape(&self, object: &dyn Shape, word_point: Point) -> Color {
-        let object_point = object.transform().inverse() * word_point;
-        let pattern_point = self.transform().inverse() * object_point;
-        self.pattern_at(pattern_point)
+        self.pattern_at(self.world_to_pattern(object, word_point, None))
+    }
+
+    /// Convert the `world_point` on the `object` to the space of the pattern.
+    /// With the world `w` the point is converted through the transformations
+    /// of any groups the object is in, without it only the transformation of
+    /// the object itself is used.
+    fn world_to_pattern(&self, object: &dyn Shape, world_point: Point, w: Option<&World>) -> Point {
+        let object_point = match w {
+            Some(w) => object.world_to_object(world_point, w),
+            None => object.transform().inverse() * world_point,
+        };
+
+        self.transform().inverse() * object_point
+    }
+
+    /// The `u` and `v` texture coordinates of the `object` at the
+    /// `world_point`, for patterns mapped onto a shape with texture
+    /// coordinates. The point is converted to the space of the pattern with
+    /// `world_to_pattern` and the `hit` is given to the object, see
+    /// [`Shape::uv_at_hit`].
+    fn uv_at_hit(
+        &self,
+        object: &dyn Shape,
+        world_point: Point,
+        hit: Option<&Intersection>,
+        w: Option<&World>,
+    ) -> (f64, f64) {
+        object.uv_at_hit(self.world_to_pattern(object, world_point, w), hit)
+    }
+
+    /// Determines the color at the point of the object the same as
+    /// `pattern_at_shape`. With the world `w` the point is converted to object
+    /// space through the transformations of any groups the object is in.
+    /// Patterns mapped with `u` and `v` texture coordinates can use the `hit`
+    /// to find the coordinates, for example the `u` and `v` of a triangle.
+    fn pattern_at_hit(
+        &self,
+        object: &dyn Shape,
+        world_point: Point,
+        _hit: Option<&Intersection>,
+        w: Option<&World>,
+    ) -> Color {
+        self.pattern_at(self.world_to_pattern(object, world_point, w))
+    }
+
+    /// Determines the color at the point of the object the same as
+    /// `pattern_at_hit`, averaged over the `footprint` of a pixel. The
+    /// footprint is the offsets across and down to the points the next pixels
+    /// hit, see [`crate::Ray::footprint`]. The bigger the footprint is in the
+    /// space of the pattern the more samples are taken, so a pattern far off in
+    /// the distance blends together instead of flickering between colors.
+    fn pattern_at_footprint(
+        &self,
+        object: &dyn Shape,
+        world_point: Point,
+        hit: Option<&Intersection>,
+        footprint: Option<(Vector, Vector)>,
+        w: Option<&World>,
+    ) -> Color {
+        let (dx, dy) = match footprint {
+            Some(footprint) => footprint,
+            None => return self.pattern_at_hit(object, world_point, hit, w),
+        };
+
+        // size of the footprint in the space of the pattern
+        let to_pattern = |p: Point| {
+            let object_point = match w {
+                Some(w) => object.world_to_object(p, w),
+                None => object.transform().inverse() * p,
+            };
+            self.transform().inverse() * object_point
+        };
+        let center = to_pattern(world_point);
+        let size = (to_pattern(world_point + dx) - center)
+            .magnitude()
+            .max((to_pattern(world_point + dy) - center).magnitude());
+
+        // about four samples across each unit of the pattern
+        let n = (size * FOOTPRINT_SAMPLES_PER_UNIT)
+            .ceil()
+            .clamp(1.0, FOOTPRINT_MAX_SAMPLES) as usize;
+        if n == 1 {
+            return self.pattern_at_hit(object, world_point, hit, w);
+        }
+
+        let mut color = Color::new(0.0, 0.0, 0.0);
+        for i in 0..n {
+            for j in 0..n {
+                let s = (i as f64 + 0.5) / n as f64 - 0.5;
+                let t = (j as f64 + 0.5) / n as f64 - 0.5;
+                color = color + self.pattern_at_hit(object, world_point + dx * s + dy * t, hit, w);
+            }
+        }
+
+        color * (1.0 / (n * n) as f64)
+    }
+}
+
+// Samples taken across a footprint for each unit of pattern space it covers.
+const FOOTPRINT_SAMPLES_PER_UNIT: f64 = 4.0;
+
+// Most samples taken across a footprint, at most the square of it in total.
+const FOOTPRINT_MAX_SAMPLES: f64 = 8.0;
+
+impl Clone for Box<dyn Pattern> {
+    fn clone(&self) -> Self {
+        self.box_clone()
     }
 }
 
@@ -75,7 +189,12 @@ impl PartialEq for Box<dyn Pattern> {
 
 #[cfg(test)]
 mod tests {
-    use crate::{patterns::TestPattern, shapes::Sphere, Transformation, IDENTITY};
+    use crate::{
+        patterns::{Stripe, TestPattern},
+        shapes::{Group, Sphere},
+        Colors, PointLight, Ray, Transformation, IDENTITY,
+    };
+    use std::f64::consts::PI;
 
     use super::*;
 
@@ -138,4 +257,54 @@ mod tests {
 
         assert_eq!(c, Color::new(0.75, 0.5, 0.25));
     }
+
+    #[test]
+    fn a_pattern_on_an_object_in_a_transformed_group() {
+        let mut w = World::new();
+        let mut g = Group::new();
+        g.transform = Transformation::new().rotate_y(PI / 2.0).build();
+        let s = Sphere::new();
+        let s_id = s.id();
+        g.add_object(Box::new(s));
+        w.add_object(Box::new(g));
+        let s = w.get_object_by_id(s_id).unwrap();
+        let pattern = Stripe::new(Colors::WHITE, Colors::BLACK);
+
+        // the group rotates the x-axis of the sphere onto the -z axis
+        let near = Point::new(0.0, 0.0, -0.5);
+        let far = Point::new(0.0, 0.0, 0.5);
+
+        assert_eq!(
+            pattern.pattern_at_hit(s, near, None, Some(&w)),
+            Colors::WHITE
+        );
+        assert_eq!(
+            pattern.pattern_at_hit(s, far, None, Some(&w)),
+            Colors::BLACK
+        );
+        assert_eq!(pattern.pattern_at_hit(s, far, None, None), Colors::WHITE);
+        assert_eq!(pattern.pattern_at_shape(s, far), Colors::WHITE);
+    }
+
+    #[test]
+    fn the_stripes_of_an_object_follow_the_rotation_of_its_group() {
+        let mut w = World::new();
+        w.light = Some(PointLight::new(
+            Point::new(0.0, 0.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        ));
+        let mut g = Group::new();
+        g.transform = Transformation::new().rotate_y(PI / 2.0).build();
+        let mut s = Sphere::new();
+        s.material.pattern = Some(Box::new(Stripe::new(Colors::WHITE, Colors::RED)));
+        s.material.ambient = 1.0;
+        s.material.diffuse = 0.0;
+        s.material.specular = 0.0;
+        g.add_object(Box::new(s));
+        w.add_object(Box::new(g));
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        // the ray hits the sphere at x = 1 in the space of the sphere
+        assert_eq!(w.color_at(r, 5), Colors::RED);
+    }
 }
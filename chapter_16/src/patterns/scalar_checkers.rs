@@ -0,0 +1,93 @@
+use super::ScalarPattern;
+use crate::id::new_id;
+use crate::{Matrix, Point, IDENTITY};
+use uuid::Uuid;
+
+/// Checker pattern of the values `a` and `b` instead of colors, such as a
+/// transparency map alternating between opaque and clear squares.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ScalarCheckers {
+    id: Uuid,
+    a: f64,
+    b: f64,
+    /// The transformation of the pattern.
+    pub transform: Matrix,
+}
+
+impl ScalarCheckers {
+    /// Create a new checkers pattern using the values `a` and `b`.
+    pub fn new(a: f64, b: f64) -> ScalarCheckers {
+        ScalarCheckers {
+            id: new_id(),
+            a,
+            b,
+            transform: IDENTITY,
+        }
+    }
+}
+
+impl ScalarPattern for ScalarCheckers {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn box_clone(&self) -> Box<dyn ScalarPattern> {
+        Box::new(*self)
+    }
+
+    fn transform(&self) -> Matrix {
+        self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+
+    fn scalar_at(&self, point: Point) -> f64 {
+        if (point.x.floor() + point.y.floor() + point.z.floor()).rem_euclid(2.0) == 0.0 {
+            self.a
+        } else {
+            self.b
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::Sphere, Transformation};
+
+    #[test]
+    fn scalar_checkers_alternate_in_each_direction() {
+        let pattern = ScalarCheckers::new(0.25, 0.75);
+        let data = vec![
+            (Point::new(0.0, 0.0, 0.0), 0.25),
+            (Point::new(1.01, 0.0, 0.0), 0.75),
+            (Point::new(0.0, 1.01, 0.0), 0.75),
+            (Point::new(0.0, 0.0, 1.01), 0.75),
+            (Point::new(-0.5, 0.0, 0.0), 0.75),
+            (Point::new(1.5, 1.5, 0.0), 0.25),
+        ];
+
+        for (point, expected) in data {
+            assert_eq!(pattern.scalar_at(point), expected);
+        }
+    }
+
+    #[test]
+    fn the_value_follows_the_object_and_pattern_transformations() {
+        let mut object = Sphere::new();
+        object.transform = Transformation::new().scale(2.0, 2.0, 2.0).build();
+        let mut pattern = ScalarCheckers::new(0.0, 1.0);
+        pattern.transform = Transformation::new().translate(0.5, 0.0, 0.0).build();
+
+        assert_eq!(
+            pattern.value_at(&object, Point::new(1.5, 0.0, 0.0), None),
+            0.0
+        );
+        assert_eq!(
+            pattern.value_at(&object, Point::new(0.5, 0.0, 0.0), None),
+            1.0
+        );
+    }
+}
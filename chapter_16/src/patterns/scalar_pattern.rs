@@ -0,0 +1,67 @@
+use crate::{shapes::Shape, Matrix, Point, World};
+use std::fmt;
+use uuid::Uuid;
+
+/// A pattern giving a single number for each point in space instead of a
+/// [`Color`](crate::Color), used to vary a property of a material such as its
+/// transparency across the surface of an object.
+pub trait ScalarPattern: fmt::Debug + Send + Sync {
+    /// Get the unique identifier for a pattern.
+    fn id(&self) -> Uuid;
+
+    /// Copy the pattern into a new [`Box`], used to clone a
+    /// [`crate::Material`] with a pattern. The copy has the same `id` as
+    /// `self`.
+    fn box_clone(&self) -> Box<dyn ScalarPattern>;
+
+    /// Test if `other` is equal to `self` by comparing their `id`'s.
+    fn pattern_eq(&self, other: &dyn ScalarPattern) -> bool {
+        self.id() == other.id()
+    }
+
+    /// Returns a pattern's [`Transformation`](crate::Transformation) [`Matrix`].
+    fn transform(&self) -> Matrix;
+
+    /// Sets a pattern's [`Transformation`](crate::Transformation) [`Matrix`].
+    fn set_transform(&mut self, transform: Matrix);
+
+    /// Determine the value of the pattern at a particular point on the
+    /// pattern.
+    fn scalar_at(&self, point: Point) -> f64;
+
+    /// Determine the value of the pattern at the world space `point` of the
+    /// `object`. The point is converted to object space, through the
+    /// transformations of any groups the object is in when given the world
+    /// `w`, then to *pattern space*.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{patterns::ScalarCheckers, patterns::ScalarPattern, shapes::Sphere, Point};
+    ///
+    /// let object = Sphere::new();
+    /// let pattern = ScalarCheckers::new(0.0, 1.0);
+    ///
+    /// assert_eq!(pattern.value_at(&object, Point::new(0.5, 0.0, 0.0), None), 0.0);
+    /// assert_eq!(pattern.value_at(&object, Point::new(1.5, 0.0, 0.0), None), 1.0);
+    /// ```
+    fn value_at(&self, object: &dyn Shape, world_point: Point, w: Option<&World>) -> f64 {
+        let object_point = match w {
+            Some(w) => object.world_to_object(world_point, w),
+            None => object.transform().inverse() * world_point,
+        };
+        self.scalar_at(self.transform().inverse() * object_point)
+    }
+}
+
+impl Clone for Box<dyn ScalarPattern> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
+}
+
+impl PartialEq for Box<dyn ScalarPattern> {
+    fn eq(&self, other: &Box<dyn ScalarPattern>) -> bool {
+        self.pattern_eq(other.as_ref())
+    }
+}
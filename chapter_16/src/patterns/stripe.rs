@@ -1,4 +1,5 @@
 use super::Pattern;
+use crate::id::new_id;
 use crate::{Color, Matrix, Point, IDENTITY};
 use uuid::Uuid;
 
@@ -28,7 +29,7 @@ impl Stripe {
     /// ```
     pub fn new(a: Color, b: Color) -> Stripe {
         Stripe {
-            id: Uuid::new_v4(),
+            id: new_id(),
             a,
             b,
             transform: IDENTITY,
@@ -41,6 +42,10 @@ impl Pattern for Stripe {
         self.id
     }
 
+    fn box_clone(&self) -> Box<dyn Pattern> {
+        Box::new(*self)
+    }
+
     fn transform(&self) -> Matrix {
         self.transform
     }
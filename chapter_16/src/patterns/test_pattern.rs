@@ -1,6 +1,8 @@
 #[cfg(test)]
 use super::Pattern;
 #[cfg(test)]
+use crate::id::new_id;
+#[cfg(test)]
 use crate::{Color, Matrix, Point, IDENTITY};
 #[cfg(test)]
 use uuid::Uuid;
@@ -16,7 +18,7 @@ pub struct TestPattern {
 impl TestPattern {
     pub fn new() -> TestPattern {
         TestPattern {
-            id: Uuid::new_v4(),
+            id: new_id(),
             transform: IDENTITY,
         }
     }
@@ -28,6 +30,10 @@ impl Pattern for TestPattern {
         self.id
     }
 
+    fn box_clone(&self) -> Box<dyn Pattern> {
+        Box::new(*self)
+    }
+
     fn set_transform(&mut self, transform: Matrix) {
         self.transform = transform;
     }
@@ -0,0 +1,139 @@
+use super::Pattern;
+use crate::id::new_id;
+use crate::{shapes::Shape, Color, Intersection, Matrix, Point, World, IDENTITY};
+use uuid::Uuid;
+
+/// Checker pattern in the two-dimensional texture coordinates `u` and `v` of
+/// a shape. Unlike [`Checkers`](super::Checkers) the squares follow the
+/// surface of the shape, `width` squares across `u` and `height` squares
+/// across `v`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct UvCheckers {
+    id: Uuid,
+    width: f64,
+    height: f64,
+    a: Color,
+    b: Color,
+    /// The transformation of the pattern.
+    pub transform: Matrix,
+}
+
+impl UvCheckers {
+    /// Create a new checkers pattern `width` squares wide and `height` squares
+    /// high using the [`Color`] `a` and `b`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{patterns::UvCheckers, Colors};
+    ///
+    /// let pattern = UvCheckers::new(2.0, 2.0, Colors::BLACK, Colors::WHITE);
+    ///
+    /// assert_eq!(pattern.uv_pattern_at(0.0, 0.0), Colors::BLACK);
+    /// assert_eq!(pattern.uv_pattern_at(0.5, 0.0), Colors::WHITE);
+    /// ```
+    pub fn new(width: f64, height: f64, a: Color, b: Color) -> UvCheckers {
+        UvCheckers {
+            id: new_id(),
+            width,
+            height,
+            a,
+            b,
+            transform: IDENTITY,
+        }
+    }
+
+    /// The color of the square at the texture coordinate `u` and `v`.
+    pub fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        let u2 = (u * self.width).floor();
+        let v2 = (v * self.height).floor();
+
+        if (u2 + v2).rem_euclid(2.0) == 0.0 {
+            self.a
+        } else {
+            self.b
+        }
+    }
+}
+
+impl Pattern for UvCheckers {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn box_clone(&self) -> Box<dyn Pattern> {
+        Box::new(*self)
+    }
+
+    fn transform(&self) -> Matrix {
+        self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+
+    /// Planar mapping of the `point`, the pattern repeats every unit along the
+    /// `x` and `z` axes.
+    fn pattern_at(&self, point: Point) -> Color {
+        self.uv_pattern_at(point.x.rem_euclid(1.0), point.z.rem_euclid(1.0))
+    }
+
+    /// Map the point onto the checkers with the texture coordinates of the
+    /// `object` at the `hit`.
+    fn pattern_at_hit(
+        &self,
+        object: &dyn Shape,
+        world_point: Point,
+        hit: Option<&Intersection>,
+        w: Option<&World>,
+    ) -> Color {
+        let (u, v) = self.uv_at_hit(object, world_point, hit, w);
+        self.uv_pattern_at(u, v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{shapes::Sphere, Colors};
+
+    // Bonus Chapter Texture Mapping
+    #[test]
+    fn checker_pattern_in_2d() {
+        let checkers = UvCheckers::new(2.0, 2.0, Colors::BLACK, Colors::WHITE);
+        let data = vec![
+            (0.0, 0.0, Colors::BLACK),
+            (0.5, 0.0, Colors::WHITE),
+            (0.0, 0.5, Colors::WHITE),
+            (0.5, 0.5, Colors::BLACK),
+            (1.0, 1.0, Colors::BLACK),
+        ];
+
+        for (u, v, expected) in data {
+            assert_eq!(checkers.uv_pattern_at(u, v), expected);
+        }
+    }
+
+    #[test]
+    fn checkers_on_a_sphere_use_the_spherical_mapping() {
+        let checkers = UvCheckers::new(16.0, 8.0, Colors::BLACK, Colors::WHITE);
+        let s = Sphere::new();
+        let data = vec![
+            (Point::new(0.4315, 0.4670, 0.7719), Colors::WHITE),
+            (Point::new(-0.9654, 0.2552, -0.0534), Colors::BLACK),
+            (Point::new(0.1039, 0.7090, 0.6975), Colors::WHITE),
+            (Point::new(-0.4986, -0.7856, -0.3663), Colors::BLACK),
+            (Point::new(-0.0317, -0.9395, 0.3411), Colors::BLACK),
+            (Point::new(0.4809, -0.7721, 0.4154), Colors::BLACK),
+            (Point::new(0.0285, -0.9612, -0.2745), Colors::BLACK),
+            (Point::new(-0.5734, -0.2162, -0.7903), Colors::WHITE),
+            (Point::new(0.7688, -0.1470, 0.6223), Colors::BLACK),
+            (Point::new(-0.7652, 0.2175, 0.6060), Colors::BLACK),
+        ];
+
+        for (point, expected) in data {
+            assert_eq!(checkers.pattern_at_hit(&s, point, None, None), expected);
+        }
+    }
+}
@@ -35,6 +35,19 @@ impl Point {
     pub fn new(x: f64, y: f64, z: f64) -> Self {
         Self { x, y, z }
     }
+
+    /// The `x`, `y` and `z` of the point as an array.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::Point;
+    ///
+    /// assert_eq!(Point::new(1.0, 2.0, 3.0).as_array(), [1.0, 2.0, 3.0]);
+    /// ```
+    pub fn as_array(&self) -> [f64; 3] {
+        [self.x, self.y, self.z]
+    }
 }
 
 impl Add<Vector> for Point {
@@ -121,6 +134,18 @@ impl Neg for Point {
     }
 }
 
+impl From<[f64; 3]> for Point {
+    fn from(a: [f64; 3]) -> Self {
+        Point::new(a[0], a[1], a[2])
+    }
+}
+
+impl From<Point> for [f64; 3] {
+    fn from(p: Point) -> Self {
+        p.as_array()
+    }
+}
+
 impl PartialEq for Point {
     fn eq(&self, other: &Self) -> bool {
         float_eq(self.x, other.x) && float_eq(self.y, other.y) && float_eq(self.z, other.z)
@@ -220,4 +245,14 @@ mod tests {
 
         assert_eq!(a / 2.0, Point::new(0.5, -1.0, 1.5));
     }
+
+    #[test]
+    fn converting_a_point_to_and_from_an_array() {
+        let p = Point::new(1.0, 2.0, 3.0);
+        let a: [f64; 3] = p.into();
+
+        assert_eq!(a, [1.0, 2.0, 3.0]);
+        assert_eq!(Point::from(a), p);
+        assert_eq!(Point::from(p.as_array()), p);
+    }
 }
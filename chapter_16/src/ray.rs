@@ -1,16 +1,23 @@
-use crate::{Matrix, Point, Vector};
+use crate::{Matrix, Point, Vector, EPSILON};
 
 /// A line which starts at a point and goes off in a particular
 /// direction to infinity.
 ///
 /// A ray will have a starting ([`Point`]) called the origin and a ([`Vector`])
 /// describing the direction of the ray.
+///
+/// A ray from a camera can also carry the *differentials* of its direction,
+/// how much the direction changes moving one pixel over (`dx`) and one pixel
+/// down (`dy`). The differentials tell how much of a surface a single pixel
+/// covers where the ray hits, so a pattern can be averaged over that area.
 #[derive(Debug, Copy, Clone)]
 pub struct Ray {
     // The origin of the ray
     pub origin: Point,
     // The direction of the ray
     pub direction: Vector,
+    // The change in direction to the next pixel across and down
+    pub differentials: Option<(Vector, Vector)>,
 }
 
 impl Ray {
@@ -29,7 +36,67 @@ impl Ray {
     /// assert_eq!(direction, r.direction);
     /// ```
     pub fn new(origin: Point, direction: Vector) -> Ray {
-        Ray { origin, direction }
+        Ray {
+            origin,
+            direction,
+            differentials: None,
+        }
+    }
+
+    /// Returns the ray with the differentials `dx` and `dy` of its direction,
+    /// the change in direction to the ray of the next pixel across and down.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Point, Ray, Vector};
+    ///
+    /// let dx = Vector::new(0.01, 0.0, 0.0);
+    /// let dy = Vector::new(0.0, -0.01, 0.0);
+    /// let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0))
+    ///     .with_differentials(dx, dy);
+    ///
+    /// assert_eq!(r.differentials, Some((dx, dy)));
+    /// ```
+    pub fn with_differentials(self, dx: Vector, dy: Vector) -> Ray {
+        Ray {
+            differentials: Some((dx, dy)),
+            ..self
+        }
+    }
+
+    /// Find the offsets from the point the ray hits at `t` to where the rays of
+    /// the next pixel across and down hit, taking the surface as flat with the
+    /// normal `normalv`. Returns `None` when the ray has no differentials or
+    /// one of the offset rays runs along the surface.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Point, Ray, Vector};
+    ///
+    /// let r = Ray::new(Point::new(0.0, 1.0, 0.0), Vector::new(0.0, -1.0, 0.0))
+    ///     .with_differentials(Vector::new(0.1, 0.0, 0.0), Vector::new(0.0, 0.0, 0.1));
+    /// let (dx, dy) = r.footprint(1.0, Vector::new(0.0, 1.0, 0.0)).unwrap();
+    ///
+    /// assert_eq!(dx, Vector::new(0.1, 0.0, 0.0));
+    /// assert_eq!(dy, Vector::new(0.0, 0.0, 0.1));
+    /// ```
+    pub fn footprint(&self, t: f64, normalv: Vector) -> Option<(Vector, Vector)> {
+        let (dx, dy) = self.differentials?;
+        let point = self.position(t);
+        let offset = |d: Vector| {
+            let direction = self.direction + d;
+            let denom = normalv.dot(direction);
+            if denom.abs() < EPSILON {
+                None
+            } else {
+                let t = normalv.dot(point - self.origin) / denom;
+                Some(self.origin + direction * t - point)
+            }
+        };
+
+        offset(dx).zip(offset(dy))
     }
 
     /// Find the position that lie any distance `t` along te ray.
@@ -50,11 +117,70 @@ impl Ray {
         self.origin + self.direction * t
     }
 
+    /// Find the position that lie any distance `t` along the ray, the same as
+    /// `position`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Point, Ray, Vector};
+    ///
+    /// let r = Ray::new(Point::new(2.0, 3.0, 4.0), Vector::new(1.0, 0.0, 0.0));
+    ///
+    /// assert_eq!(r.at(2.5), Point::new(4.5, 3.0, 4.0));
+    /// ```
+    pub fn at(&self, t: f64) -> Point {
+        self.position(t)
+    }
+
+    /// Returns a ray with the same origin pointing in the opposite direction.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Point, Ray, Vector};
+    ///
+    /// let r = Ray::new(Point::new(2.0, 3.0, 4.0), Vector::new(1.0, 0.0, 0.0));
+    /// let reversed = r.reversed();
+    ///
+    /// assert_eq!(reversed.origin, Point::new(2.0, 3.0, 4.0));
+    /// assert_eq!(reversed.direction, Vector::new(-1.0, 0.0, 0.0));
+    /// ```
+    pub fn reversed(&self) -> Ray {
+        Ray {
+            direction: -self.direction,
+            differentials: self.differentials.map(|(dx, dy)| (-dx, -dy)),
+            ..*self
+        }
+    }
+
+    /// Returns a ray with the origin moved `epsilon` along the direction of the
+    /// ray, used to keep a ray from intersecting the surface it starts on.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Point, Ray, Vector};
+    ///
+    /// let r = Ray::new(Point::new(2.0, 3.0, 4.0), Vector::new(0.0, 2.0, 0.0));
+    ///
+    /// assert_eq!(r.advance(0.5).origin, Point::new(2.0, 4.0, 4.0));
+    /// ```
+    pub fn advance(&self, epsilon: f64) -> Ray {
+        Ray {
+            origin: self.position(epsilon),
+            ..*self
+        }
+    }
+
     pub fn transform(&self, transformation: Matrix) -> Ray {
-        Ray::new(
-            transformation * self.origin,
-            transformation * self.direction,
-        )
+        Ray {
+            origin: transformation * self.origin,
+            direction: transformation * self.direction,
+            differentials: self
+                .differentials
+                .map(|(dx, dy)| (transformation * dx, transformation * dy)),
+        }
     }
 }
 
@@ -110,4 +236,57 @@ mod tests {
         assert_eq!(r2.origin, Point::new(2.0, 6.0, 12.0));
         assert_eq!(r2.direction, Vector::new(0.0, 3.0, 0.0));
     }
+
+    #[test]
+    fn at_is_the_same_as_position() {
+        let r = Ray::new(Point::new(2.0, 3.0, 4.0), Vector::new(1.0, -2.0, 0.5));
+
+        for t in [-1.0, 0.0, 1.0, 2.5] {
+            assert_eq!(r.at(t), r.position(t));
+        }
+    }
+
+    #[test]
+    fn reversing_a_ray() {
+        let r = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::new(0.0, 1.0, -1.0));
+        let r2 = r.reversed();
+
+        assert_eq!(r2.origin, Point::new(1.0, 2.0, 3.0));
+        assert_eq!(r2.direction, Vector::new(0.0, -1.0, 1.0));
+    }
+
+    #[test]
+    fn advancing_a_ray() {
+        let r = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::new(0.0, 0.6, 0.8));
+        let r2 = r.advance(0.01);
+
+        assert_eq!(r2.origin, Point::new(1.0, 2.006, 3.008));
+        assert_eq!(r2.direction, r.direction);
+    }
+
+    #[test]
+    fn transforming_a_ray_transforms_its_differentials() {
+        let r = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::new(0.0, 1.0, 0.0))
+            .with_differentials(Vector::new(0.1, 0.0, 0.0), Vector::new(0.0, 0.0, 0.1));
+        let m = Transformation::new().scale(2.0, 3.0, 4.0).build();
+        let r2 = r.transform(m);
+
+        assert_eq!(
+            r2.differentials,
+            Some((Vector::new(0.2, 0.0, 0.0), Vector::new(0.0, 0.0, 0.4)))
+        );
+        assert!(Ray::new(r.origin, r.direction).differentials.is_none());
+    }
+
+    #[test]
+    fn the_footprint_of_a_ray_grows_on_a_slanted_surface() {
+        let r = Ray::new(Point::new(0.0, 1.0, 0.0), Vector::new(0.0, -1.0, 0.0))
+            .with_differentials(Vector::new(0.1, 0.0, 0.0), Vector::new(0.0, 0.0, 0.1));
+        let n = Vector::new(1.0, 1.0, 0.0).normalize();
+        let (dx, dy) = r.footprint(1.0, n).unwrap();
+
+        assert!(dx.magnitude() > 0.1);
+        assert_eq!(dy, Vector::new(0.0, 0.0, 0.1));
+        assert!(Ray::new(r.origin, r.direction).footprint(1.0, n).is_none());
+    }
 }
@@ -0,0 +1,108 @@
+/// A small pseudo random number generator, a xorshift64* generator.
+///
+/// The same seed always produces the same sequence of numbers, so anything
+/// rendered with random jitter can be reproduced exactly.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Create a new generator starting with the given `seed`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::Rng;
+    ///
+    /// let mut a = Rng::new(42);
+    /// let mut b = Rng::new(42);
+    ///
+    /// assert_eq!(a.next_f64(), b.next_f64());
+    /// ```
+    pub fn new(seed: u64) -> Rng {
+        let mut rng = Rng { state: 0 };
+        rng.seed(seed);
+        rng
+    }
+
+    /// Restart the sequence of numbers from the given `seed`.
+    pub fn seed(&mut self, seed: u64) {
+        // scramble the seed so small seeds still start far apart, the state
+        // of a xorshift generator can never be zero.
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        self.state = if z == 0 { 0x9E37_79B9_7F4A_7C15 } else { z };
+    }
+
+    /// The next number in the sequence.
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// The next number in the sequence as a number from `0.0` up to but not
+    /// including `1.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::Rng;
+    ///
+    /// let mut rng = Rng::new(7);
+    /// let n = rng.next_f64();
+    ///
+    /// assert!((0.0..1.0).contains(&n));
+    /// ```
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_same_seed_produces_the_same_numbers() {
+        let mut a = Rng::new(1234);
+        let mut b = Rng::new(1);
+        b.seed(1234);
+
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn different_seeds_produce_different_numbers() {
+        let mut a = Rng::new(1);
+        let mut b = Rng::new(2);
+        let a: Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+        let b: Vec<u64> = (0..10).map(|_| b.next_u64()).collect();
+
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn random_numbers_are_between_zero_and_one() {
+        let mut rng = Rng::new(99);
+        let numbers: Vec<f64> = (0..1000).map(|_| rng.next_f64()).collect();
+
+        assert!(numbers.iter().all(|n| (0.0..1.0).contains(n)));
+        assert!(numbers.iter().any(|n| *n < 0.1));
+        assert!(numbers.iter().any(|n| *n > 0.9));
+    }
+}
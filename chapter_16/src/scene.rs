@@ -0,0 +1,779 @@
+use crate::{
+    patterns::{Checkers, Gradient, Pattern, Ring, Stripe},
+    shapes::{Cone, Cube, Cylinder, Group, Plane, Shape, Sphere},
+    Camera, Color, Material, Point, PointLight, Transformation, Vector, World,
+};
+use std::{collections::HashMap, fmt, iter::Peekable, str::Chars};
+
+/// Errors found while building a scene from a YAML description.
+#[derive(Debug, PartialEq)]
+pub enum SceneError {
+    /// The line number of the YAML description that could not be parsed.
+    Syntax(usize),
+    /// A required key, such as the `camera`, is missing.
+    Missing(String),
+    /// The value of the key could not be read.
+    Invalid(String),
+    /// Reference to a name that was never given with `define`.
+    Undefined(String),
+    /// An `add` of something the scene does not know how to build.
+    Unknown(String),
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneError::Syntax(line) => write!(f, "syntax error on line {}", line),
+            SceneError::Missing(key) => write!(f, "missing {}", key),
+            SceneError::Invalid(key) => write!(f, "invalid value for {}", key),
+            SceneError::Undefined(name) => write!(f, "{} is not defined", name),
+            SceneError::Unknown(name) => write!(f, "unknown item {}", name),
+        }
+    }
+}
+
+impl std::error::Error for SceneError {}
+
+/// A node of the subset of YAML used to describe a scene.
+#[derive(Debug, Clone, PartialEq)]
+enum Yaml {
+    Scalar(String),
+    List(Vec<Yaml>),
+    Map(Vec<(String, Yaml)>),
+}
+
+impl Yaml {
+    /// Get the value of a `key` from a map, the last entry for a `key` wins.
+    fn get(&self, key: &str) -> Option<&Yaml> {
+        match self {
+            Yaml::Map(entries) => entries.iter().rev().find(|e| e.0 == key).map(|e| &e.1),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Yaml::Scalar(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+struct Line {
+    number: usize,
+    indent: usize,
+    text: String,
+}
+
+/// Parses block sequences, block mappings, flow sequences and scalars, which
+/// is all the book's scene files use.
+struct YamlParser {
+    lines: Vec<Line>,
+    pos: usize,
+}
+
+impl YamlParser {
+    fn parse(buffer: &str) -> Result<Yaml, SceneError> {
+        let lines = buffer
+            .lines()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                let text = YamlParser::strip_comment(line).trim_end();
+                let trimmed = text.trim_start();
+                if trimmed.is_empty() || trimmed == "---" {
+                    None
+                } else {
+                    Some(Line {
+                        number: i + 1,
+                        indent: text.len() - trimmed.len(),
+                        text: trimmed.to_string(),
+                    })
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut parser = YamlParser { lines, pos: 0 };
+        let document = match parser.lines.first() {
+            Some(line) => parser.parse_block(line.indent)?,
+            None => Yaml::List(Vec::new()),
+        };
+
+        match parser.lines.get(parser.pos) {
+            Some(line) => Err(SceneError::Syntax(line.number)),
+            None => Ok(document),
+        }
+    }
+
+    fn strip_comment(line: &str) -> &str {
+        let mut previous = ' ';
+        for (i, c) in line.char_indices() {
+            if c == '#' && previous.is_whitespace() {
+                return &line[..i];
+            }
+            previous = c;
+        }
+
+        line
+    }
+
+    fn is_item(text: &str) -> bool {
+        text == "-" || text.starts_with("- ")
+    }
+
+    fn split_key(text: &str) -> Option<(&str, &str)> {
+        if text.starts_with('[') {
+            None
+        } else if let Some(i) = text.find(": ") {
+            Some((text[..i].trim(), text[i + 2..].trim()))
+        } else {
+            text.strip_suffix(':').map(|key| (key.trim(), ""))
+        }
+    }
+
+    fn parse_block(&mut self, indent: usize) -> Result<Yaml, SceneError> {
+        if YamlParser::is_item(&self.lines[self.pos].text) {
+            self.parse_list(indent)
+        } else {
+            self.parse_map(indent)
+        }
+    }
+
+    fn parse_nested(&mut self, indent: usize, number: usize) -> Result<Yaml, SceneError> {
+        match self.lines.get(self.pos) {
+            Some(next) if next.indent > indent => {
+                let indent = next.indent;
+                self.parse_block(indent)
+            }
+            _ => Err(SceneError::Syntax(number)),
+        }
+    }
+
+    fn parse_list(&mut self, indent: usize) -> Result<Yaml, SceneError> {
+        let mut items = Vec::new();
+
+        while let Some(line) = self.lines.get(self.pos) {
+            if line.indent < indent || (line.indent == indent && !YamlParser::is_item(&line.text)) {
+                break;
+            }
+            let number = line.number;
+            if line.indent > indent {
+                return Err(SceneError::Syntax(number));
+            }
+
+            let rest = line.text[1..].trim_start().to_string();
+            if rest.is_empty() {
+                self.pos += 1;
+                items.push(self.parse_nested(indent, number)?);
+            } else if YamlParser::split_key(&rest).is_some() {
+                // a map starting on the same line as the `-` of the item
+                let line = &mut self.lines[self.pos];
+                line.indent += line.text.len() - rest.len();
+                line.text = rest;
+                let indent = line.indent;
+                items.push(self.parse_map(indent)?);
+            } else {
+                self.pos += 1;
+                items.push(YamlParser::parse_flow(&rest, number)?);
+            }
+        }
+
+        Ok(Yaml::List(items))
+    }
+
+    fn parse_map(&mut self, indent: usize) -> Result<Yaml, SceneError> {
+        let mut entries = Vec::new();
+
+        while let Some(line) = self.lines.get(self.pos) {
+            if line.indent < indent || (line.indent == indent && YamlParser::is_item(&line.text)) {
+                break;
+            }
+            let number = line.number;
+            if line.indent > indent {
+                return Err(SceneError::Syntax(number));
+            }
+
+            let (key, rest) =
+                YamlParser::split_key(&line.text).ok_or(SceneError::Syntax(number))?;
+            let (key, rest) = (key.to_string(), rest.to_string());
+            self.pos += 1;
+
+            let value = if !rest.is_empty() {
+                YamlParser::parse_flow(&rest, number)?
+            } else {
+                match self.lines.get(self.pos) {
+                    // a list can start at the same indent as its key
+                    Some(next) if next.indent == indent && YamlParser::is_item(&next.text) => {
+                        self.parse_list(indent)?
+                    }
+                    _ => self.parse_nested(indent, number)?,
+                }
+            };
+            entries.push((key, value));
+        }
+
+        Ok(Yaml::Map(entries))
+    }
+
+    fn parse_flow(text: &str, number: usize) -> Result<Yaml, SceneError> {
+        if !text.starts_with('[') {
+            return Ok(YamlParser::scalar(text));
+        }
+
+        let mut chars = text.chars().peekable();
+        let value = YamlParser::parse_flow_value(&mut chars, number)?;
+        if chars.any(|c| !c.is_whitespace()) {
+            Err(SceneError::Syntax(number))
+        } else {
+            Ok(value)
+        }
+    }
+
+    fn parse_flow_value(chars: &mut Peekable<Chars>, number: usize) -> Result<Yaml, SceneError> {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        if chars.next_if_eq(&'[').is_none() {
+            let mut text = String::new();
+            while let Some(c) = chars.next_if(|c| *c != ',' && *c != ']') {
+                text.push(c);
+            }
+            return Ok(YamlParser::scalar(&text));
+        }
+
+        let mut items = Vec::new();
+        loop {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if chars.next_if_eq(&']').is_some() {
+                break;
+            }
+            items.push(YamlParser::parse_flow_value(chars, number)?);
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            match chars.next() {
+                Some(',') => continue,
+                Some(']') => break,
+                _ => return Err(SceneError::Syntax(number)),
+            }
+        }
+
+        Ok(Yaml::List(items))
+    }
+
+    fn scalar(text: &str) -> Yaml {
+        let text = text.trim();
+        let unquoted = text
+            .strip_prefix('"')
+            .and_then(|t| t.strip_suffix('"'))
+            .or_else(|| text.strip_prefix('\'').and_then(|t| t.strip_suffix('\'')));
+
+        Yaml::Scalar(unquoted.unwrap_or(text).to_string())
+    }
+}
+
+/// Build a [`Camera`] and a [`World`] from a YAML scene description in the
+/// format used by the book.
+///
+/// The description is a list of items. Items with `add` create a `camera`, a
+/// `light`, or a shape (`sphere`, `plane`, `cube`, `cylinder`, `cone`, or
+/// `group`) with an optional `transform` and `material`. Items with `define`
+/// give a name to a `value` which can then be used in place of a material,
+/// transform or shape. A `define` can `extend` another definition.
+pub struct Scene {}
+
+impl Scene {
+    /// Parse a YAML scene description returning the [`Camera`] and [`World`]
+    /// it describes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Color, Scene};
+    ///
+    /// let yaml = "
+    /// - add: camera
+    ///   width: 100
+    ///   height: 50
+    ///   field-of-view: 0.785
+    ///   from: [0, 1.5, -5]
+    ///   to: [0, 1, 0]
+    ///   up: [0, 1, 0]
+    /// - add: light
+    ///   at: [-10, 10, -10]
+    ///   intensity: [1, 1, 1]
+    /// - add: sphere
+    ///   material:
+    ///     color: [1, 0.2, 1]
+    /// ";
+    /// let (camera, world) = Scene::from_yaml(yaml).unwrap();
+    ///
+    /// assert_eq!(camera.hsize, 100);
+    /// let color = world.get_object(0).unwrap().material().color;
+    /// assert_eq!(color, Color::new(1.0, 0.2, 1.0));
+    /// ```
+    pub fn from_yaml(buffer: &str) -> Result<(Camera, World), SceneError> {
+        let items = match YamlParser::parse(buffer)? {
+            Yaml::List(items) => items,
+            _ => return Err(SceneError::Invalid("scene".to_string())),
+        };
+
+        let mut defines: HashMap<String, Yaml> = HashMap::new();
+        let mut camera = None;
+        let mut world = World::new();
+
+        for item in &items {
+            if let Some(name) = item.get("define") {
+                let name = Scene::string(name, "define")?;
+                let value = Scene::required(item, "value")?;
+                let value = match item.get("extend") {
+                    Some(base) => {
+                        let base = Scene::string(base, "extend")?;
+                        Scene::extend(Scene::lookup(&defines, base)?, value)
+                    }
+                    None => value.clone(),
+                };
+                defines.insert(name.to_string(), value);
+            } else if let Some(kind) = item.get("add") {
+                match Scene::string(kind, "add")? {
+                    "camera" => camera = Some(Scene::camera(item)?),
+                    "light" => world.light = Some(Scene::light(item)?),
+                    _ => {
+                        world.add_object(Scene::shape(item, &defines)?);
+                    }
+                }
+            } else {
+                return Err(SceneError::Missing("add or define".to_string()));
+            }
+        }
+
+        let camera = camera.ok_or_else(|| SceneError::Missing("camera".to_string()))?;
+
+        Ok((camera, world))
+    }
+
+    fn camera(item: &Yaml) -> Result<Camera, SceneError> {
+        let width = Scene::number(Scene::required(item, "width")?, "width")?;
+        let height = Scene::number(Scene::required(item, "height")?, "height")?;
+        let fov = Scene::number(Scene::required(item, "field-of-view")?, "field-of-view")?;
+        let (x, y, z) = Scene::triple(Scene::required(item, "from")?, "from")?;
+        let from = Point::new(x, y, z);
+        let (x, y, z) = Scene::triple(Scene::required(item, "to")?, "to")?;
+        let to = Point::new(x, y, z);
+        let (x, y, z) = Scene::triple(Scene::required(item, "up")?, "up")?;
+        let up = Vector::new(x, y, z);
+
+        let mut camera = Camera::new(width as usize, height as usize, fov);
+        camera.transform = Transformation::view_transform(from, to, up);
+
+        Ok(camera)
+    }
+
+    fn light(item: &Yaml) -> Result<PointLight, SceneError> {
+        let (x, y, z) = Scene::triple(Scene::required(item, "at")?, "at")?;
+        let intensity = Scene::color(Scene::required(item, "intensity")?, "intensity")?;
+
+        Ok(PointLight::new(Point::new(x, y, z), intensity))
+    }
+
+    fn shape(item: &Yaml, defines: &HashMap<String, Yaml>) -> Result<Box<dyn Shape>, SceneError> {
+        let kind = Scene::string(Scene::required(item, "add")?, "add")?;
+
+        let mut shape: Box<dyn Shape> = match kind {
+            "sphere" => Box::new(Sphere::new()),
+            "plane" => Box::new(Plane::new()),
+            "cube" => Box::new(Cube::new()),
+            "cylinder" => {
+                let mut c = Cylinder::new();
+                (c.minimum, c.maximum, c.closed) = Scene::extents(item, c.minimum, c.maximum)?;
+                Box::new(c)
+            }
+            "cone" => {
+                let mut c = Cone::new();
+                (c.minimum, c.maximum, c.closed) = Scene::extents(item, c.minimum, c.maximum)?;
+                Box::new(c)
+            }
+            "group" => {
+                let mut g = Group::new();
+                if let Some(children) = item.get("children") {
+                    for child in Scene::list(children, "children")? {
+                        g.add_object(Scene::shape(child, defines)?);
+                    }
+                }
+                Box::new(g)
+            }
+            name => {
+                // a defined shape, the item adds to or overrides its keys
+                let defined = Scene::lookup(defines, name)?;
+                if defined.get("add").is_none() {
+                    return Err(SceneError::Unknown(name.to_string()));
+                }
+                let entries = match item {
+                    Yaml::Map(entries) => entries.iter().filter(|e| e.0 != "add").cloned(),
+                    _ => return Err(SceneError::Invalid("add".to_string())),
+                };
+                let item = Scene::extend(defined, &Yaml::Map(entries.collect()));
+                return Scene::shape(&item, defines);
+            }
+        };
+
+        if let Some(transform) = item.get("transform") {
+            let t = Scene::transform(Transformation::new(), transform, defines)?;
+            shape.set_transform(t.build());
+        }
+        if let Some(material) = item.get("material") {
+            shape.set_material(Scene::material(material, defines)?);
+        }
+
+        Ok(shape)
+    }
+
+    fn extents(item: &Yaml, minimum: f64, maximum: f64) -> Result<(f64, f64, bool), SceneError> {
+        let minimum = match item.get("min") {
+            Some(v) => Scene::number(v, "min")?,
+            None => minimum,
+        };
+        let maximum = match item.get("max") {
+            Some(v) => Scene::number(v, "max")?,
+            None => maximum,
+        };
+        let closed = match item.get("closed") {
+            Some(v) => Scene::boolean(v, "closed")?,
+            None => false,
+        };
+
+        Ok((minimum, maximum, closed))
+    }
+
+    fn transform(
+        mut t: Transformation,
+        value: &Yaml,
+        defines: &HashMap<String, Yaml>,
+    ) -> Result<Transformation, SceneError> {
+        for op in Scene::list(value, "transform")? {
+            let args = match op {
+                Yaml::Scalar(name) => {
+                    t = Scene::transform(t, Scene::lookup(defines, name)?, defines)?;
+                    continue;
+                }
+                Yaml::List(args) if !args.is_empty() => args,
+                _ => return Err(SceneError::Invalid("transform".to_string())),
+            };
+
+            let name = Scene::string(&args[0], "transform")?;
+            let n = args[1..]
+                .iter()
+                .map(|a| Scene::number(a, name))
+                .collect::<Result<Vec<_>, _>>()?;
+            let arity = match name {
+                "translate" | "scale" => 3,
+                "rotate-x" | "rotate-y" | "rotate-z" => 1,
+                "shear" => 6,
+                _ => return Err(SceneError::Unknown(name.to_string())),
+            };
+            if n.len() != arity {
+                return Err(SceneError::Invalid(name.to_string()));
+            }
+
+            t = match name {
+                "translate" => t.translate(n[0], n[1], n[2]),
+                "scale" => t.scale(n[0], n[1], n[2]),
+                "rotate-x" => t.rotate_x(n[0]),
+                "rotate-y" => t.rotate_y(n[0]),
+                "rotate-z" => t.rotate_z(n[0]),
+                _ => t.shear(n[0], n[1], n[2], n[3], n[4], n[5]),
+            };
+        }
+
+        Ok(t)
+    }
+
+    fn material(value: &Yaml, defines: &HashMap<String, Yaml>) -> Result<Material, SceneError> {
+        let entries = match value {
+            Yaml::Scalar(name) => return Scene::material(Scene::lookup(defines, name)?, defines),
+            Yaml::Map(entries) => entries,
+            _ => return Err(SceneError::Invalid("material".to_string())),
+        };
+
+        let mut m = Material::new();
+        for (key, v) in entries {
+            match key.as_str() {
+                "color" => m.color = Scene::color(v, key)?,
+                "ambient" => m.ambient = Scene::number(v, key)?,
+                "diffuse" => m.diffuse = Scene::number(v, key)?,
+                "specular" => m.specular = Scene::number(v, key)?,
+                "shininess" => m.shininess = Scene::number(v, key)?,
+                "reflective" => m.reflective = Scene::number(v, key)?,
+                "metallic" => m.metallic = Scene::boolean(v, key)?,
+                "reflect-color" => m.reflect_color = Some(Scene::color(v, key)?),
+                "transparency" => m.transparency = Scene::number(v, key)?,
+                "refractive-index" => m.refractive_index = Scene::number(v, key)?,
+                "emissive" => m.emissive = Scene::color(v, key)?,
+                "casts-shadow" => m.casts_shadow = Scene::boolean(v, key)?,
+                "pattern" => m.pattern = Some(Scene::pattern(v, defines)?),
+                _ => return Err(SceneError::Unknown(key.to_string())),
+            }
+        }
+
+        Ok(m)
+    }
+
+    fn pattern(
+        value: &Yaml,
+        defines: &HashMap<String, Yaml>,
+    ) -> Result<Box<dyn Pattern>, SceneError> {
+        let kind = Scene::string(Scene::required(value, "type")?, "type")?;
+        let colors = Scene::list(Scene::required(value, "colors")?, "colors")?;
+        if colors.len() != 2 {
+            return Err(SceneError::Invalid("colors".to_string()));
+        }
+        let a = Scene::color(&colors[0], "colors")?;
+        let b = Scene::color(&colors[1], "colors")?;
+
+        let mut pattern: Box<dyn Pattern> = match kind {
+            "stripes" => Box::new(Stripe::new(a, b)),
+            "gradient" => Box::new(Gradient::new(a, b)),
+            "rings" => Box::new(Ring::new(a, b)),
+            "checkers" => Box::new(Checkers::new(a, b)),
+            _ => return Err(SceneError::Unknown(kind.to_string())),
+        };
+
+        if let Some(transform) = value.get("transform") {
+            let t = Scene::transform(Transformation::new(), transform, defines)?;
+            pattern.set_transform(t.build());
+        }
+
+        Ok(pattern)
+    }
+
+    /// Combine the `value` of a definition with the `base` it extends.
+    fn extend(base: &Yaml, value: &Yaml) -> Yaml {
+        match (base, value) {
+            (Yaml::Map(base), Yaml::Map(value)) => {
+                Yaml::Map(base.iter().chain(value.iter()).cloned().collect())
+            }
+            (Yaml::List(base), Yaml::List(value)) => {
+                Yaml::List(base.iter().chain(value.iter()).cloned().collect())
+            }
+            _ => value.clone(),
+        }
+    }
+
+    fn lookup<'a>(defines: &'a HashMap<String, Yaml>, name: &str) -> Result<&'a Yaml, SceneError> {
+        defines
+            .get(name)
+            .ok_or_else(|| SceneError::Undefined(name.to_string()))
+    }
+
+    fn required<'a>(item: &'a Yaml, key: &str) -> Result<&'a Yaml, SceneError> {
+        item.get(key)
+            .ok_or_else(|| SceneError::Missing(key.to_string()))
+    }
+
+    fn string<'a>(value: &'a Yaml, key: &str) -> Result<&'a str, SceneError> {
+        value
+            .as_str()
+            .ok_or_else(|| SceneError::Invalid(key.to_string()))
+    }
+
+    fn list<'a>(value: &'a Yaml, key: &str) -> Result<&'a [Yaml], SceneError> {
+        match value {
+            Yaml::List(items) => Ok(items),
+            _ => Err(SceneError::Invalid(key.to_string())),
+        }
+    }
+
+    fn number(value: &Yaml, key: &str) -> Result<f64, SceneError> {
+        value
+            .as_str()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| SceneError::Invalid(key.to_string()))
+    }
+
+    fn boolean(value: &Yaml, key: &str) -> Result<bool, SceneError> {
+        value
+            .as_str()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| SceneError::Invalid(key.to_string()))
+    }
+
+    fn triple(value: &Yaml, key: &str) -> Result<(f64, f64, f64), SceneError> {
+        match Scene::list(value, key)? {
+            [x, y, z] => Ok((
+                Scene::number(x, key)?,
+                Scene::number(y, key)?,
+                Scene::number(z, key)?,
+            )),
+            _ => Err(SceneError::Invalid(key.to_string())),
+        }
+    }
+
+    fn color(value: &Yaml, key: &str) -> Result<Color, SceneError> {
+        let (r, g, b) = Scene::triple(value, key)?;
+        Ok(Color::new(r, g, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Colors, Ray};
+    use std::f64::consts::PI;
+
+    const MINIMAL: &str = "
+# a camera, a light and a single sphere
+- add: camera
+  width: 100
+  height: 50
+  field-of-view: 0.785
+  from: [ 0, 0, -5 ]
+  to: [ 0, 0, 0 ]
+  up: [ 0, 1, 0 ]
+
+- add: light
+  at: [ -10, 10, -10 ]
+  intensity: [ 1, 1, 1 ]
+
+- add: sphere
+  material:
+    color: [ 0.8, 1.0, 0.6 ]
+    diffuse: 0.7
+    specular: 0.2
+  transform:
+    - [ scale, 2, 2, 2 ]
+";
+
+    #[test]
+    fn loading_a_minimal_scene() {
+        let (camera, world) = Scene::from_yaml(MINIMAL).unwrap();
+
+        assert_eq!(camera.hsize, 100);
+        assert_eq!(camera.vsize, 50);
+        let light = world.light.unwrap();
+        assert_eq!(light.position, Point::new(-10.0, 10.0, -10.0));
+        assert_eq!(light.intensity, Colors::WHITE);
+
+        assert!(world.get_object(1).is_none());
+        let s = world.get_object(0).unwrap();
+        assert_eq!(s.material().color, Color::new(0.8, 1.0, 0.6));
+        assert_eq!(s.material().diffuse, 0.7);
+        assert_eq!(s.material().specular, 0.2);
+        assert_eq!(s.material().ambient, 0.1);
+        assert_eq!(
+            s.transform(),
+            Transformation::new().scale(2.0, 2.0, 2.0).build()
+        );
+    }
+
+    #[test]
+    fn defines_can_extend_materials_and_transforms() {
+        let yaml = "
+- add: camera
+  width: 10
+  height: 10
+  field-of-view: 1.0
+  from: [ 0, 0, -5 ]
+  to: [ 0, 0, 0 ]
+  up: [ 0, 1, 0 ]
+- define: white-material
+  value:
+    color: [ 1, 1, 1 ]
+    diffuse: 0.7
+    reflective: 0.1
+- define: blue-material
+  extend: white-material
+  value:
+    color: [ 0.537, 0.831, 0.914 ]
+- define: standard-transform
+  value:
+    - [ translate, 1, -1, 1 ]
+    - [ scale, 0.5, 0.5, 0.5 ]
+- define: large-object
+  value:
+    - standard-transform
+    - [ scale, 3.5, 3.5, 3.5 ]
+- add: cube
+  material: blue-material
+  transform:
+    - large-object
+    - [ translate, 4, 0, 0 ]
+";
+        let (_, world) = Scene::from_yaml(yaml).unwrap();
+        let cube = world.get_object(0).unwrap();
+        let expected = Transformation::new()
+            .translate(1.0, -1.0, 1.0)
+            .scale(0.5, 0.5, 0.5)
+            .scale(3.5, 3.5, 3.5)
+            .translate(4.0, 0.0, 0.0)
+            .build();
+
+        assert_eq!(cube.material().color, Color::new(0.537, 0.831, 0.914));
+        assert_eq!(cube.material().diffuse, 0.7);
+        assert_eq!(cube.material().reflective, 0.1);
+        assert_eq!(cube.transform(), expected);
+    }
+
+    #[test]
+    fn loading_groups_and_patterns() {
+        let yaml = "
+- add: camera
+  width: 10
+  height: 10
+  field-of-view: 1.0
+  from: [ 0, 0, -5 ]
+  to: [ 0, 0, 0 ]
+  up: [ 0, 1, 0 ]
+- add: group
+  transform:
+    - [ rotate-y, 3.141592653589793 ]
+  children:
+    - add: cylinder
+      min: 0
+      max: 1
+      closed: true
+    - add: plane
+      material:
+        pattern:
+          type: checkers
+          colors:
+            - [ 1, 1, 1 ]
+            - [ 0, 0, 0 ]
+          transform:
+            - [ scale, 0.25, 0.25, 0.25 ]
+";
+        let (_, world) = Scene::from_yaml(yaml).unwrap();
+        let g = world.get_object(0).unwrap();
+        let g = g.as_any().unwrap().downcast_ref::<Group>().unwrap();
+
+        assert_eq!(g.transform, Transformation::new().rotate_y(PI).build());
+        assert_eq!(g.objects().len(), 2);
+        let p = g.get_object(1).unwrap();
+        let pattern = p.material().pattern.as_ref().unwrap();
+        assert_eq!(
+            pattern.transform(),
+            Transformation::new().scale(0.25, 0.25, 0.25).build()
+        );
+        assert_eq!(pattern.pattern_at(Point::new(0.0, 0.0, 0.0)), Colors::WHITE);
+        assert_eq!(pattern.pattern_at(Point::new(1.1, 0.0, 0.0)), Colors::BLACK);
+
+        let r = Ray::new(Point::new(0.0, 0.5, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = world.intersect_world(r).unwrap();
+        assert_eq!(xs.len(), 2);
+    }
+
+    #[test]
+    fn errors_loading_a_scene() {
+        assert_eq!(
+            Scene::from_yaml("- add: light\n  at: [0, 0, 0]\n  intensity: [1, 1, 1]\n").err(),
+            Some(SceneError::Missing("camera".to_string()))
+        );
+        assert_eq!(
+            Scene::from_yaml("- add: sphere\n  material: shiny\n").err(),
+            Some(SceneError::Undefined("shiny".to_string()))
+        );
+        assert_eq!(
+            Scene::from_yaml("- add: teapot\n").err(),
+            Some(SceneError::Undefined("teapot".to_string()))
+        );
+        assert_eq!(
+            Scene::from_yaml("- add: sphere\n  transform: [[scale, 1, 2]\n").err(),
+            Some(SceneError::Syntax(2))
+        );
+    }
+}
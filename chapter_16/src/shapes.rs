@@ -6,6 +6,7 @@ mod cube;
 mod cylinder;
 mod group;
 mod plane;
+mod polygon;
 mod shape;
 mod smooth_triangles;
 mod sphere;
@@ -15,11 +16,12 @@ mod triangle;
 pub use cone::Cone;
 pub use csg::CsgOperation;
 pub use csg::CSG;
-pub use cube::Cube;
+pub use cube::{Cube, CubeFace};
 pub use cylinder::Cylinder;
 pub use group::Group;
 pub use plane::Plane;
-pub use shape::Shape;
+pub use polygon::Polygon;
+pub use shape::{Shape, ShapeExt};
 pub use sphere::Sphere;
 pub use triangle::Triangle;
 
@@ -1,14 +1,19 @@
+use std::any::Any;
+
 use super::Shape;
+use crate::id::new_id;
 #[allow(unused_imports)]
 use crate::Transformation;
-use crate::{float_eq, Intersection, Material, Matrix, Point, Ray, Vector, EPSILON, IDENTITY};
+use crate::{
+    float_eq, Bounds, Intersection, Material, Matrix, Point, Ray, Vector, EPSILON, IDENTITY,
+};
 use std::f64::{INFINITY, NEG_INFINITY};
 use uuid::Uuid;
 
 /// Not a cone in the natural sense but a double-napped code. Two cones
 /// "nose to nose", with one cone balanced perfectly on the other.
 ///
-/// A `Cone` has a default radius of 1 unit and are infinity in both `+y` and
+/// A `Cone` has a default `radius` of 1 unit at a `y` of 1 and are infinity in both `+y` and
 /// `-y`. `Cone` can be truncated in either `y` direction or both. They can
 /// also be opened at each end or closed. By default they are open.
 #[derive(Debug)]
@@ -25,27 +30,60 @@ pub struct Cone {
     pub minimum: f64,
     /// Determine is the ends of the `Cone` are open or close
     pub closed: bool,
+    /// Distance from the y-axis to the sides at a `y` of 1, the radius grows
+    /// by this much for every unit along the y-axis
+    pub radius: f64,
 }
 
 impl Cone {
     pub fn new() -> Cone {
         Cone {
-            id: Uuid::new_v4(),
+            id: new_id(),
             parent_id: None,
             transform: IDENTITY,
             material: Material::new(),
             minimum: NEG_INFINITY,
             maximum: INFINITY,
             closed: false,
+            radius: 1.0,
+        }
+    }
+
+    /// Create a new `Cone` truncated at `min` and `max` along the y-axis,
+    /// with the ends closed if `closed` is `true`.
+    ///
+    /// In a debug build this panics if `min` is greater than `max`, a `Cone`
+    /// like that is never intersected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::shapes::Cone;
+    ///
+    /// let c = Cone::truncated(-0.5, 0.5, true);
+    ///
+    /// assert_eq!(c.minimum, -0.5);
+    /// assert_eq!(c.maximum, 0.5);
+    /// assert!(c.closed);
+    /// ```
+    pub fn truncated(min: f64, max: f64, closed: bool) -> Cone {
+        debug_assert!(min <= max, "minimum must not be greater than maximum");
+        Cone {
+            minimum: min,
+            maximum: max,
+            closed,
+            ..Cone::new()
         }
     }
 
-    fn check_cap(&self, ray: Ray, t: f64) -> bool {
+    // The radius of a cone at `y` is the absolute value of `y` times the
+    // `radius`, the cap at `y` is hit if the ray at `t` is within that radius
+    // of the axis.
+    fn check_cap(&self, ray: Ray, t: f64, y: f64) -> bool {
         let x = ray.origin.x + t * ray.direction.x;
         let z = ray.origin.z + t * ray.direction.z;
-        let y = ray.origin.y + t * ray.direction.y;
 
-        x.powi(2) + z.powi(2) <= y.abs()
+        x.powi(2) + z.powi(2) <= (self.radius * y).powi(2)
     }
 
     fn intersect_caps(&self, ray: Ray) -> Option<Vec<Intersection>> {
@@ -56,12 +94,12 @@ impl Cone {
         }
 
         let t = (self.minimum - ray.origin.y) / ray.direction.y;
-        if self.check_cap(ray, t) {
+        if self.check_cap(ray, t, self.minimum) {
             xs.push(Intersection::new(t, self));
         }
 
         let t = (self.maximum - ray.origin.y) / ray.direction.y;
-        if self.check_cap(ray, t) {
+        if self.check_cap(ray, t, self.maximum) {
             xs.push(Intersection::new(t, self));
         }
 
@@ -92,6 +130,10 @@ impl Shape for Cone {
         self.parent_id = Some(id);
     }
 
+    fn clear_parent_id(&mut self) {
+        self.parent_id = None;
+    }
+
     fn transform(&self) -> Matrix {
         self.transform
     }
@@ -115,12 +157,14 @@ impl Shape for Cone {
     fn local_intersect(&self, ray: Ray) -> Option<Vec<Intersection>> {
         let mut xs: Vec<Intersection> = Vec::new();
 
-        let a = ray.direction.x.powi(2) - ray.direction.y.powi(2) + ray.direction.z.powi(2);
+        let r2 = self.radius.powi(2);
+
+        let a = ray.direction.x.powi(2) - r2 * ray.direction.y.powi(2) + ray.direction.z.powi(2);
 
-        let b = 2.0 * ray.origin.x * ray.direction.x - 2.0 * ray.origin.y * ray.direction.y
+        let b = 2.0 * ray.origin.x * ray.direction.x - 2.0 * r2 * ray.origin.y * ray.direction.y
             + 2.0 * ray.origin.z * ray.direction.z;
 
-        let c = ray.origin.x.powi(2) - ray.origin.y.powi(2) + ray.origin.z.powi(2);
+        let c = ray.origin.x.powi(2) - r2 * ray.origin.y.powi(2) + ray.origin.z.powi(2);
 
         if float_eq(a, 0.0) && float_eq(b, 0.0) {
             return None;
@@ -167,21 +211,41 @@ impl Shape for Cone {
         }
     }
 
+    /// The radius of a cone is the largest of the absolute values of the
+    /// `minimum` and `maximum` times the `radius`.
+    fn bounds(&self) -> Bounds {
+        let limit = self.radius * self.minimum.abs().max(self.maximum.abs());
+
+        Bounds::new(
+            Point::new(-limit, self.minimum, -limit),
+            Point::new(limit, self.maximum, limit),
+        )
+    }
+
     fn local_normal_at(&self, point: Point, _hit: Option<&Intersection>) -> Vector {
         let dist = point.x.powi(2) + point.z.powi(2);
+        let cap = |y: f64| (self.radius * y).powi(2);
 
-        if dist < 1.0 && point.y >= self.maximum - EPSILON {
+        if dist < cap(self.maximum) && point.y >= self.maximum - EPSILON {
             Vector::new(0.0, 1.0, 0.0)
-        } else if dist < 1.0 && point.y <= self.minimum + EPSILON {
+        } else if dist < cap(self.minimum) && point.y <= self.minimum + EPSILON {
             Vector::new(0.0, -1.0, 0.0)
         } else if point.y > 0.0 {
-            let y = -((point.x.powi(2) + point.z.powi(2)).sqrt());
+            let y = -self.radius * (point.x.powi(2) + point.z.powi(2)).sqrt();
             Vector::new(point.x, y, point.z)
         } else {
-            let y = (point.x.powi(2) + point.z.powi(2)).sqrt();
+            let y = self.radius * (point.x.powi(2) + point.z.powi(2)).sqrt();
             Vector::new(point.x, y, point.z)
         }
     }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
 }
 
 impl PartialEq for Cone {
@@ -283,4 +347,90 @@ mod tests {
             assert_eq!(n, rec.1);
         }
     }
+
+    #[test]
+    fn a_truncated_cone_intersects_the_same_as_setting_the_fields() {
+        let t = Cone::truncated(-0.5, 0.5, true);
+        let mut c = Cone::new();
+        c.minimum = -0.5;
+        c.maximum = 0.5;
+        c.closed = true;
+        let data = vec![
+            (Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 1.0, 0.0)),
+            (Point::new(0.0, 0.0, -0.25), Vector::new(0.0, 1.0, 1.0)),
+            (Point::new(0.0, 0.0, -0.25), Vector::new(0.0, 1.0, 0.0)),
+            (Point::new(0.0, 0.2, -5.0), Vector::new(0.0, 0.0, 1.0)),
+        ];
+
+        for (origin, direction) in data {
+            let r = Ray::new(origin, direction.normalize());
+            let expected: Option<Vec<f64>> = c
+                .local_intersect(r)
+                .map(|xs| xs.iter().map(|x| x.t).collect());
+            let actual: Option<Vec<f64>> = t
+                .local_intersect(r)
+                .map(|xs| xs.iter().map(|x| x.t).collect());
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "minimum must not be greater than maximum")]
+    fn a_truncated_cone_with_swapped_bounds() {
+        Cone::truncated(0.5, -0.5, false);
+    }
+
+    #[test]
+    fn a_bounded_cone_has_a_bounding_box() {
+        let c = Cone::truncated(-5.0, 3.0, false);
+        let b = c.bounds();
+
+        assert_eq!(b.min, Point::new(-5.0, -5.0, -5.0));
+        assert_eq!(b.max, Point::new(5.0, 3.0, 5.0));
+        assert!(Cone::new().bounds().is_infinite());
+    }
+
+    #[test]
+    fn the_caps_of_a_cone_have_the_radius_of_the_cone_at_their_y() {
+        let c = Cone::truncated(-2.0, 2.0, true);
+        let data = vec![
+            (Point::new(1.5, 5.0, 0.0), vec![3.0, 3.5, 6.5, 7.0]),
+            (Point::new(0.0, 5.0, 1.99), vec![3.0, 3.01, 6.99, 7.0]),
+            (Point::new(0.0, 5.0, -2.01), vec![]),
+            (Point::new(2.5, 5.0, 0.0), vec![]),
+        ];
+
+        for (origin, expected) in data {
+            let r = Ray::new(origin, Vector::new(0.0, -1.0, 0.0));
+            let mut ts: Vec<f64> = c
+                .local_intersect(r)
+                .unwrap_or_default()
+                .iter()
+                .map(|i| i.t)
+                .collect();
+            ts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            assert_eq!(ts.len(), expected.len());
+            for (t, e) in ts.iter().zip(expected) {
+                assert!(float_eq(*t, e));
+            }
+        }
+    }
+
+    #[test]
+    fn a_ray_hits_a_cone_with_a_smaller_radius() {
+        let mut c = Cone::new();
+        c.radius = 0.5;
+        let r = Ray::new(Point::new(0.0, 2.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = c.local_intersect(r).unwrap();
+
+        assert_eq!(xs.len(), 2);
+        assert!(float_eq(xs[0].t, 4.0));
+        assert!(float_eq(xs[1].t, 6.0));
+        assert_eq!(
+            c.local_normal_at(Point::new(0.0, 2.0, -1.0), None),
+            Vector::new(0.0, -0.5, -1.0)
+        );
+    }
 }
@@ -1,5 +1,8 @@
+use std::any::Any;
+
 use super::Shape;
-use crate::{Intersection, Material, Matrix, Point, Ray, Vector, IDENTITY};
+use crate::id::new_id;
+use crate::{Bounds, Intersection, Intersections, Material, Matrix, Point, Ray, Vector, IDENTITY};
 use uuid::Uuid;
 
 #[derive(Debug)]
@@ -26,7 +29,7 @@ impl CSG {
         mut left: Box<dyn Shape>,
         mut right: Box<dyn Shape>,
     ) -> Self {
-        let id = Uuid::new_v4();
+        let id = new_id();
         left.set_parent_id(id);
         right.set_parent_id(id);
         CSG {
@@ -40,6 +43,16 @@ impl CSG {
         }
     }
 
+    /// The shape on the left side of the operation.
+    pub fn left(&self) -> &dyn Shape {
+        self.left.as_ref()
+    }
+
+    /// The shape on the right side of the operation.
+    pub fn right(&self) -> &dyn Shape {
+        self.right.as_ref()
+    }
+
     pub fn filter_intersections<'a>(&'a self, xs: &'a [Intersection]) -> Vec<Intersection> {
         let mut inl = false;
         let mut inr = false;
@@ -87,6 +100,10 @@ impl Shape for CSG {
         self.parent_id = Some(id);
     }
 
+    fn clear_parent_id(&mut self) {
+        self.parent_id = None;
+    }
+
     fn transform(&self) -> Matrix {
         self.transform
     }
@@ -107,28 +124,35 @@ impl Shape for CSG {
         self.material = material;
     }
 
+    fn get_object_by_id(&self, id: Uuid) -> Option<&dyn Shape> {
+        for s in [&self.left, &self.right] {
+            if s.id() == id {
+                return Some(s.as_ref());
+            }
+            if let Some(c) = s.get_object_by_id(id) {
+                return Some(c);
+            }
+        }
+
+        None
+    }
+
     fn contains_object_by_id(&self, id: Uuid) -> bool {
-        self.left.id() == id || self.right.id() == id
+        [&self.left, &self.right]
+            .iter()
+            .any(|s| s.id() == id || s.contains_object_by_id(id))
     }
 
     fn local_intersect(&self, ray: Ray) -> Option<Vec<Intersection>> {
-        let mut xs: Vec<Intersection> = Vec::new();
-
-        if let Some(left_xs) = self.left.intersect(ray) {
-            for i in left_xs {
-                xs.push(i);
-            }
-        }
-
-        if let Some(right_xs) = self.right.intersect(ray) {
-            for i in right_xs {
-                xs.push(i);
-            }
-        }
+        let xs: Intersections = self
+            .left
+            .intersect(ray)
+            .into_iter()
+            .chain(self.right.intersect(ray))
+            .flatten()
+            .collect();
 
         if !xs.is_empty() {
-            xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
-
             let mut inl = false;
             let mut inr = false;
 
@@ -155,17 +179,68 @@ impl Shape for CSG {
         }
     }
 
-    fn local_normal_at(&self, point: Point, _hit: Option<&Intersection>) -> Vector {
-        Vector::new(point.x, point.y, point.z)
+    /// The box around both the left and right children, even though a
+    /// difference or intersection may be smaller.
+    fn bounds(&self) -> Bounds {
+        self.left
+            .parent_space_bounds()
+            .merge(self.right.parent_space_bounds())
+    }
+
+    /// The normal is the normal of the child shape the `hit` belongs to, the
+    /// `point` is converted into the space of the child. Without a `hit` the
+    /// child with its bounds nearest to the `point` is used.
+    fn local_normal_at(&self, point: Point, hit: Option<&Intersection>) -> Vector {
+        let child = match hit {
+            Some(hit) => {
+                let id = hit.object.id();
+                if self.left.id() == id || self.left.contains_object_by_id(id) {
+                    &self.left
+                } else {
+                    &self.right
+                }
+            }
+            None => {
+                let left = distance_to_bounds(self.left.parent_space_bounds(), point);
+                let right = distance_to_bounds(self.right.parent_space_bounds(), point);
+                if left <= right {
+                    &self.left
+                } else {
+                    &self.right
+                }
+            }
+        };
+
+        child.normal_at(point, hit, None)
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
     }
 }
 
+// The distance from the `point` to the surface of the box `bounds`, zero when
+// the point is inside of the box.
+fn distance_to_bounds(bounds: Bounds, point: Point) -> f64 {
+    let dx = (bounds.min.x - point.x)
+        .max(point.x - bounds.max.x)
+        .max(0.0);
+    let dy = (bounds.min.y - point.y)
+        .max(point.y - bounds.max.y)
+        .max(0.0);
+    let dz = (bounds.min.z - point.z)
+        .max(point.z - bounds.max.z)
+        .max(0.0);
+
+    (dx * dx + dy * dy + dz * dz).sqrt()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         shapes::{Cube, Sphere},
-        Intersection, Transformation,
+        Intersection, Transformation, World,
     };
 
     // Chapter 16 Constructive Solid Geometry (CSG)
@@ -286,4 +361,100 @@ mod tests {
         assert_eq!(xs[1].t, 6.5);
         assert_eq!(xs[1].object.id(), s2_id);
     }
+
+    #[test]
+    fn the_normal_of_a_csg_difference_is_the_normal_of_the_child_hit() {
+        let left = Cube::new();
+        let mut right = Cube::new();
+        right.transform = Transformation::new().translate(0.0, 0.0, -1.5).build();
+        let mut c = CSG::new(CsgOperation::Difference, Box::new(left), Box::new(right));
+        c.transform = Transformation::new().translate(0.0, 3.0, 0.0).build();
+        let r = Ray::new(Point::new(0.3, 3.2, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = c.intersect(r).unwrap();
+        let point = r.position(xs[0].t);
+
+        assert_eq!(xs.len(), 2);
+        assert_eq!(point, Point::new(0.3, 3.2, -0.5));
+        // the face of the right cube carved out of the left cube
+        assert_eq!(
+            c.normal_at(point, Some(&xs[0]), None),
+            Vector::new(0.0, 0.0, 1.0)
+        );
+        assert_eq!(
+            c.normal_at(r.position(xs[1].t), Some(&xs[1]), None),
+            Vector::new(0.0, 0.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn finding_a_shape_nested_in_csg_in_a_world() {
+        let a = Sphere::new();
+        let b = Cube::new();
+        let b_id = b.id();
+        let c = Sphere::new();
+        let difference = CSG::new(CsgOperation::Difference, Box::new(a), Box::new(b));
+        let difference_id = difference.id();
+        let union = CSG::new(CsgOperation::Union, Box::new(difference), Box::new(c));
+        let mut w = World::new();
+        w.add_object(Box::new(union));
+
+        let b = w.get_object_by_id(b_id).expect("Shape not found!");
+
+        assert_eq!(b.id(), b_id);
+        assert_eq!(b.parent_id(), Some(difference_id));
+        assert!(w.get_object(0).unwrap().contains_object_by_id(b_id));
+        assert!(w.get_object_by_id(Uuid::new_v4()).is_none());
+    }
+
+    #[test]
+    fn a_ray_hits_a_shape_nested_in_csg() {
+        let a = Sphere::new();
+        let mut b = Cube::new();
+        b.transform = Transformation::new().translate(0.0, 0.0, 1.5).build();
+        let mut c = Sphere::new();
+        c.transform = Transformation::new().translate(0.0, 0.0, 5.0).build();
+        let difference = CSG::new(CsgOperation::Difference, Box::new(a), Box::new(b));
+        let union = CSG::new(CsgOperation::Union, Box::new(difference), Box::new(c));
+        let mut w = World::new();
+        w.add_object(Box::new(union));
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = w.intersect_world(r).unwrap();
+        let n = xs[1]
+            .object
+            .normal_at(r.position(xs[1].t), Some(&xs[1]), Some(&w));
+
+        assert_eq!(xs.len(), 4);
+        assert_eq!(xs[0].t, 4.0);
+        assert_eq!(xs[1].t, 5.5);
+        assert_eq!(n, Vector::new(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn a_csg_shape_has_a_bounding_box_that_contains_its_children() {
+        let left = Sphere::new();
+        let mut right = Sphere::new();
+        right.transform = Transformation::new().translate(2.0, 3.0, 4.0).build();
+        let c = CSG::new(CsgOperation::Difference, Box::new(left), Box::new(right));
+        let b = c.bounds();
+
+        assert_eq!(b.min, Point::new(-1.0, -1.0, -1.0));
+        assert_eq!(b.max, Point::new(3.0, 4.0, 5.0));
+    }
+
+    #[test]
+    fn the_normal_without_a_hit_is_from_the_nearest_child() {
+        let s1 = Sphere::new();
+        let mut s2 = Sphere::new();
+        s2.transform = Transformation::new().translate(5.0, 0.0, 0.0).build();
+        let c = CSG::new(CsgOperation::Union, Box::new(s1), Box::new(s2));
+
+        assert_eq!(
+            c.local_normal_at(Point::new(-1.0, 0.0, 0.0), None),
+            Vector::new(-1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            c.local_normal_at(Point::new(6.0, 0.0, 0.0), None),
+            Vector::new(1.0, 0.0, 0.0)
+        );
+    }
 }
@@ -1,11 +1,17 @@
+use std::any::Any;
+
 use super::Shape;
+use crate::id::new_id;
 #[allow(unused_imports)]
 use crate::Transformation;
-use crate::{float_cmp, Intersection, Material, Matrix, Point, Ray, Vector, IDENTITY};
+use crate::{float_cmp, Bounds, Intersection, Material, Matrix, Point, Ray, Vector, IDENTITY};
 use uuid::Uuid;
 
 /// A three-dimensional solid object bounded by six square sides, with three
 /// meeting at each vertex. A default cube is 1 unit size in all directions.
+///
+/// The extents of a cube can be changed with [`Cube::with_bounds`] to create
+/// an axis-aligned box without having to scale it.
 #[derive(Debug)]
 pub struct Cube {
     id: Uuid,
@@ -14,22 +20,125 @@ pub struct Cube {
     pub transform: Matrix,
     /// [`Material`] describing the look of the `Cube`
     pub material: Material,
+    /// Minimum corner of the `Cube` defined in object space
+    pub minimum: Point,
+    /// Maximum corner of the `Cube` defined in object space
+    pub maximum: Point,
+}
+
+/// One of the six faces of a [`Cube`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CubeFace {
+    /// Face in the `-x` direction.
+    Left,
+    /// Face in the `+x` direction.
+    Right,
+    /// Face in the `+y` direction.
+    Up,
+    /// Face in the `-y` direction.
+    Down,
+    /// Face in the `+z` direction.
+    Front,
+    /// Face in the `-z` direction.
+    Back,
 }
 
 impl Cube {
     /// Create a new cube.
     pub fn new() -> Cube {
         Cube {
-            id: Uuid::new_v4(),
+            id: new_id(),
             parent_id: None,
             transform: IDENTITY,
             material: Material::new(),
+            minimum: Point::new(-1.0, -1.0, -1.0),
+            maximum: Point::new(1.0, 1.0, 1.0),
         }
     }
 
-    fn check_axis(&self, origin: f64, direction: f64) -> (f64, f64) {
-        let tmin_numerator = -1.0 - origin;
-        let tmax_numerator = 1.0 - origin;
+    /// Create a new axis-aligned box with the corners `min` and `max`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{shapes::Cube, Point};
+    ///
+    /// let c = Cube::with_bounds(Point::new(0.0, 0.0, 0.0), Point::new(2.0, 2.0, 2.0));
+    ///
+    /// assert_eq!(c.minimum, Point::new(0.0, 0.0, 0.0));
+    /// assert_eq!(c.maximum, Point::new(2.0, 2.0, 2.0));
+    /// ```
+    pub fn with_bounds(min: Point, max: Point) -> Cube {
+        Cube {
+            minimum: min,
+            maximum: max,
+            ..Cube::new()
+        }
+    }
+
+    /// The face of the cube a `point` in object space is on, the face along
+    /// the axis with the largest coordinate of the `point`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{shapes::Cube, shapes::CubeFace, Point};
+    ///
+    /// let c = Cube::new();
+    ///
+    /// assert_eq!(c.face_from_point(Point::new(-1.0, 0.5, -0.25)), CubeFace::Left);
+    /// assert_eq!(c.face_from_point(Point::new(0.5, 1.0, 0.9)), CubeFace::Up);
+    /// ```
+    pub fn face_from_point(&self, point: Point) -> CubeFace {
+        let point = self.to_unit_cube(point);
+        let coord = point.x.abs().max(point.y.abs()).max(point.z.abs());
+
+        if coord == point.x {
+            CubeFace::Right
+        } else if coord == -point.x {
+            CubeFace::Left
+        } else if coord == point.y {
+            CubeFace::Up
+        } else if coord == -point.y {
+            CubeFace::Down
+        } else if coord == point.z {
+            CubeFace::Front
+        } else {
+            CubeFace::Back
+        }
+    }
+
+    /// Map a `point` in object space onto the given `face`, giving the `u` and
+    /// `v` texture coordinates on that face from `0.0` to `1.0`. Used to put a
+    /// different texture on each face of the cube.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{shapes::Cube, shapes::CubeFace, Point};
+    ///
+    /// let c = Cube::new();
+    ///
+    /// assert_eq!(c.face_uv_at(CubeFace::Front, Point::new(-0.5, 0.5, 1.0)), (0.25, 0.75));
+    /// ```
+    pub fn face_uv_at(&self, face: CubeFace, point: Point) -> (f64, f64) {
+        let point = self.to_unit_cube(point);
+
+        let (u, v) = match face {
+            CubeFace::Right => (1.0 - point.z, point.y + 1.0),
+            CubeFace::Left => (point.z + 1.0, point.y + 1.0),
+            CubeFace::Up => (point.x + 1.0, 1.0 - point.z),
+            CubeFace::Down => (point.x + 1.0, point.z + 1.0),
+            CubeFace::Front => (point.x + 1.0, point.y + 1.0),
+            CubeFace::Back => (1.0 - point.x, point.y + 1.0),
+        };
+
+        (u.rem_euclid(2.0) / 2.0, v.rem_euclid(2.0) / 2.0)
+    }
+
+    fn check_axis(&self, origin: f64, direction: f64, min: f64, max: f64) -> (f64, f64) {
+        let tmin_numerator = min - origin;
+        let tmax_numerator = max - origin;
 
         let tmin = tmin_numerator / direction;
         let tmax = tmax_numerator / direction;
@@ -40,6 +149,19 @@ impl Cube {
             (tmin, tmax)
         }
     }
+
+    // Map a point relative to the bounds of the cube onto the default unit
+    // cube, the faces of the cube end up at -1 and 1 on each axis.
+    fn to_unit_cube(&self, point: Point) -> Point {
+        let (min, max) = (self.minimum, self.maximum);
+        let scale = |p: f64, min: f64, max: f64| (2.0 * p - min - max) / (max - min);
+
+        Point::new(
+            scale(point.x, min.x, max.x),
+            scale(point.y, min.y, max.y),
+            scale(point.z, min.z, max.z),
+        )
+    }
 }
 
 impl Default for Cube {
@@ -61,6 +183,10 @@ impl Shape for Cube {
         self.parent_id = Some(id);
     }
 
+    fn clear_parent_id(&mut self) {
+        self.parent_id = None;
+    }
+
     fn transform(&self) -> Matrix {
         self.transform
     }
@@ -82,9 +208,10 @@ impl Shape for Cube {
     }
 
     fn local_intersect(&self, ray: Ray) -> Option<Vec<Intersection>> {
-        let (xtmin, xtmax) = self.check_axis(ray.origin.x, ray.direction.x);
-        let (ytmin, ytmax) = self.check_axis(ray.origin.y, ray.direction.y);
-        let (ztmin, ztmax) = self.check_axis(ray.origin.z, ray.direction.z);
+        let (min, max) = (self.minimum, self.maximum);
+        let (xtmin, xtmax) = self.check_axis(ray.origin.x, ray.direction.x, min.x, max.x);
+        let (ytmin, ytmax) = self.check_axis(ray.origin.y, ray.direction.y, min.y, max.y);
+        let (ztmin, ztmax) = self.check_axis(ray.origin.z, ray.direction.z, min.z, max.z);
 
         let min_values = [xtmin, ytmin, ztmin];
         let tmin = min_values.iter().max_by(|x, y| float_cmp(**x, **y));
@@ -104,7 +231,14 @@ impl Shape for Cube {
         }
     }
 
+    fn bounds(&self) -> Bounds {
+        Bounds::empty()
+            .add_point(self.minimum)
+            .add_point(self.maximum)
+    }
+
     fn local_normal_at(&self, point: Point, _hit: Option<&Intersection>) -> Vector {
+        let point = self.to_unit_cube(point);
         let max_values = [point.x.abs(), point.y.abs(), point.z.abs()];
         let maxc = max_values.iter().max_by(|x, y| float_cmp(**x, **y));
 
@@ -118,11 +252,29 @@ impl Shape for Cube {
             Vector::new(0.0, 0.0, point.z)
         }
     }
+
+    /// Cube mapping, the face with the largest coordinate of the `point` is
+    /// found and the `point` is mapped onto that face. Each face is a square
+    /// going from `0.0` to `1.0` in `u` and `v`.
+    fn uv_at(&self, point: Point) -> (f64, f64) {
+        self.face_uv_at(self.face_from_point(point), point)
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
 }
 
 impl PartialEq for Cube {
     fn eq(&self, other: &Self) -> bool {
-        self.transform == other.transform && self.material == other.material
+        self.transform == other.transform
+            && self.material == other.material
+            && self.minimum == other.minimum
+            && self.maximum == other.maximum
     }
 }
 
@@ -238,4 +390,117 @@ mod tests {
             assert_eq!(rec.1, normal);
         }
     }
+
+    // Bonus Chapter Texture Mapping
+    #[test]
+    fn uv_mapping_the_faces_of_a_cube() {
+        let c = Cube::new();
+        let data = vec![
+            // front
+            (Point::new(-0.5, 0.5, 1.0), (0.25, 0.75)),
+            (Point::new(0.5, -0.5, 1.0), (0.75, 0.25)),
+            // back
+            (Point::new(0.5, 0.5, -1.0), (0.25, 0.75)),
+            (Point::new(-0.5, -0.5, -1.0), (0.75, 0.25)),
+            // left
+            (Point::new(-1.0, 0.5, -0.5), (0.25, 0.75)),
+            (Point::new(-1.0, -0.5, 0.5), (0.75, 0.25)),
+            // right
+            (Point::new(1.0, 0.5, 0.5), (0.25, 0.75)),
+            (Point::new(1.0, -0.5, -0.5), (0.75, 0.25)),
+            // up
+            (Point::new(-0.5, 1.0, -0.5), (0.25, 0.75)),
+            (Point::new(0.5, 1.0, 0.5), (0.75, 0.25)),
+            // down
+            (Point::new(-0.5, -1.0, 0.5), (0.25, 0.75)),
+            (Point::new(0.5, -1.0, -0.5), (0.75, 0.25)),
+        ];
+
+        for rec in data {
+            assert_eq!(c.uv_at(rec.0), rec.1);
+        }
+    }
+
+    // Bonus Chapter Texture Mapping
+    #[test]
+    fn identifying_the_face_of_a_cube_from_a_point() {
+        let c = Cube::new();
+        let data = vec![
+            (Point::new(-1.0, 0.5, -0.25), CubeFace::Left),
+            (Point::new(1.1, -0.75, 0.8), CubeFace::Right),
+            (Point::new(0.1, 0.6, 0.9), CubeFace::Front),
+            (Point::new(-0.7, 0.0, -2.0), CubeFace::Back),
+            (Point::new(0.5, 1.0, 0.9), CubeFace::Up),
+            (Point::new(-0.2, -1.3, 1.1), CubeFace::Down),
+        ];
+
+        for (point, face) in data {
+            assert_eq!(c.face_from_point(point), face);
+        }
+    }
+
+    #[test]
+    fn identifying_the_face_of_a_cube_with_bounds() {
+        let c = Cube::with_bounds(Point::new(0.0, 0.0, 0.0), Point::new(4.0, 2.0, 2.0));
+
+        assert_eq!(c.face_from_point(Point::new(3.9, 2.0, 1.0)), CubeFace::Up);
+        assert_eq!(
+            c.face_from_point(Point::new(4.0, 1.0, 1.5)),
+            CubeFace::Right
+        );
+        assert_eq!(
+            c.face_uv_at(CubeFace::Up, Point::new(3.0, 2.0, 1.5)),
+            (0.75, 0.25)
+        );
+    }
+
+    #[test]
+    fn a_ray_intersects_a_cube_with_bounds() {
+        let c = Cube::with_bounds(Point::new(0.0, 0.0, 0.0), Point::new(2.0, 2.0, 2.0));
+        let data = vec![
+            (
+                Point::new(5.0, 1.0, 1.0),
+                Vector::new(-1.0, 0.0, 0.0),
+                3.0,
+                5.0,
+            ),
+            (
+                Point::new(1.0, -5.0, 1.5),
+                Vector::new(0.0, 1.0, 0.0),
+                5.0,
+                7.0,
+            ),
+            (
+                Point::new(1.0, 1.0, 1.0),
+                Vector::new(0.0, 0.0, 1.0),
+                -1.0,
+                1.0,
+            ),
+        ];
+        for rec in data {
+            let r = Ray::new(rec.0, rec.1);
+            let xs = c.local_intersect(r).unwrap();
+            assert_eq!(2, xs.len());
+            assert_eq!(xs[0].t, rec.2);
+            assert_eq!(xs[1].t, rec.3);
+        }
+
+        let r = Ray::new(Point::new(-0.5, 1.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(None, c.local_intersect(r));
+    }
+
+    #[test]
+    fn the_normal_on_the_surface_of_a_cube_with_bounds() {
+        let c = Cube::with_bounds(Point::new(0.0, 0.0, 0.0), Point::new(2.0, 2.0, 2.0));
+        let data = vec![
+            (Point::new(2.0, 0.5, 1.2), Vector::new(1.0, 0.0, 0.0)),
+            (Point::new(0.0, 1.5, 0.4), Vector::new(-1.0, 0.0, 0.0)),
+            (Point::new(0.6, 2.0, 1.1), Vector::new(0.0, 1.0, 0.0)),
+            (Point::new(1.3, 0.7, 0.0), Vector::new(0.0, 0.0, -1.0)),
+        ];
+        for rec in data {
+            let normal = c.local_normal_at(rec.0, None);
+            assert_eq!(normal, rec.1);
+        }
+    }
 }
@@ -1,14 +1,19 @@
+use std::any::Any;
+
 use super::Shape;
+use crate::id::new_id;
 #[allow(unused_imports)]
 use crate::Transformation;
-use crate::{float_eq, Intersection, Material, Matrix, Point, Ray, Vector, EPSILON, IDENTITY};
-use std::f64::{INFINITY, NEG_INFINITY};
+use crate::{
+    float_eq, Bounds, Intersection, Material, Matrix, Point, Ray, Vector, EPSILON, IDENTITY,
+};
+use std::f64::{consts::PI, INFINITY, NEG_INFINITY};
 use uuid::Uuid;
 
 /// A solid geometric figure with straight parallel sides and a circular or oval
 /// cross section.
 ///
-/// A cylinder has a default radius of 1 unit and are infinity in both `+y` and
+/// A cylinder has a default `radius` of 1 unit and are infinity in both `+y` and
 /// `-y`. Cylinders can be truncated in either `y` direction or both. They can
 /// also be opened at each end or closed. By default they are open.
 #[derive(Debug)]
@@ -25,6 +30,8 @@ pub struct Cylinder {
     pub minimum: f64,
     /// Determine is the ends of the cylinder are open or close
     pub closed: bool,
+    /// Distance from the y-axis to the sides defined in object space
+    pub radius: f64,
 }
 
 impl Default for Cylinder {
@@ -37,13 +44,41 @@ impl Cylinder {
     /// Create a new `Cylinder`.
     pub fn new() -> Cylinder {
         Cylinder {
-            id: Uuid::new_v4(),
+            id: new_id(),
             parent_id: None,
             transform: IDENTITY,
             material: Material::new(),
             minimum: NEG_INFINITY,
             maximum: INFINITY,
             closed: false,
+            radius: 1.0,
+        }
+    }
+
+    /// Create a new `Cylinder` truncated at `min` and `max` along the y-axis,
+    /// with the ends closed if `closed` is `true`.
+    ///
+    /// In a debug build this panics if `min` is greater than `max`, a `Cylinder`
+    /// like that is never intersected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::shapes::Cylinder;
+    ///
+    /// let c = Cylinder::truncated(1.0, 2.0, true);
+    ///
+    /// assert_eq!(c.minimum, 1.0);
+    /// assert_eq!(c.maximum, 2.0);
+    /// assert!(c.closed);
+    /// ```
+    pub fn truncated(min: f64, max: f64, closed: bool) -> Cylinder {
+        debug_assert!(min <= max, "minimum must not be greater than maximum");
+        Cylinder {
+            minimum: min,
+            maximum: max,
+            closed,
+            ..Cylinder::new()
         }
     }
 
@@ -51,7 +86,7 @@ impl Cylinder {
         let x = ray.origin.x + t * ray.direction.x;
         let z = ray.origin.z + t * ray.direction.z;
 
-        x.powi(2) + z.powi(2) <= 1.0
+        x.powi(2) + z.powi(2) <= self.radius.powi(2)
     }
 
     fn intersect_caps(&self, ray: Ray) -> Option<Vec<Intersection>> {
@@ -92,6 +127,10 @@ impl Shape for Cylinder {
         self.parent_id = Some(id);
     }
 
+    fn clear_parent_id(&mut self) {
+        self.parent_id = None;
+    }
+
     fn shape_eq(&self, other: &dyn Shape) -> bool {
         self.id == other.id()
     }
@@ -124,7 +163,7 @@ impl Shape for Cylinder {
         }
 
         let b = 2.0 * ray.origin.x * ray.direction.x + 2.0 * ray.origin.z * ray.direction.z;
-        let c = ray.origin.x.powi(2) + ray.origin.z.powi(2) - 1.0;
+        let c = ray.origin.x.powi(2) + ray.origin.z.powi(2) - self.radius.powi(2);
 
         let disc = b.powi(2) - 4.0 * a * c;
 
@@ -166,17 +205,42 @@ impl Shape for Cylinder {
         }
     }
 
+    fn bounds(&self) -> Bounds {
+        Bounds::new(
+            Point::new(-self.radius, self.minimum, -self.radius),
+            Point::new(self.radius, self.maximum, self.radius),
+        )
+    }
+
     fn local_normal_at(&self, point: Point, _hit: Option<&Intersection>) -> Vector {
         let dist = point.x.powi(2) + point.z.powi(2);
+        let radius = self.radius.powi(2);
 
-        if dist < 1.0 && point.y >= self.maximum - EPSILON {
+        if dist < radius && point.y >= self.maximum - EPSILON {
             Vector::new(0.0, 1.0, 0.0)
-        } else if dist < 1.0 && point.y <= self.minimum + EPSILON {
+        } else if dist < radius && point.y <= self.minimum + EPSILON {
             Vector::new(0.0, -1.0, 0.0)
         } else {
             Vector::new(point.x, 0.0, point.z)
         }
     }
+
+    /// Cylindrical mapping, `u` wraps around the `y` axis and `v` repeats
+    /// every unit along the `y` axis.
+    fn uv_at(&self, point: Point) -> (f64, f64) {
+        let theta = point.x.atan2(point.z);
+        let raw_u = theta / (2.0 * PI);
+
+        (1.0 - (raw_u + 0.5), point.y.rem_euclid(1.0))
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
 }
 
 impl PartialEq for Cylinder {
@@ -356,4 +420,86 @@ mod tests {
             assert_eq!(rec.1, n);
         }
     }
+
+    // Bonus Chapter Texture Mapping
+    #[test]
+    fn using_a_cylindrical_mapping_on_a_3d_point() {
+        let c = Cylinder::new();
+        let r = 2_f64.sqrt() / 2.0;
+        let data = vec![
+            (Point::new(0.0, 0.0, -1.0), (0.0, 0.0)),
+            (Point::new(0.0, 0.5, -1.0), (0.0, 0.5)),
+            (Point::new(0.0, 1.0, -1.0), (0.0, 0.0)),
+            (Point::new(r, 0.5, -r), (0.125, 0.5)),
+            (Point::new(1.0, 0.5, 0.0), (0.25, 0.5)),
+            (Point::new(r, 0.5, r), (0.375, 0.5)),
+            (Point::new(0.0, -0.25, 1.0), (0.5, 0.75)),
+            (Point::new(-r, 0.5, r), (0.625, 0.5)),
+            (Point::new(-1.0, 1.25, 0.0), (0.75, 0.25)),
+            (Point::new(-r, 0.5, -r), (0.875, 0.5)),
+        ];
+
+        for rec in data {
+            let (u, v) = c.uv_at(rec.0);
+            assert!(float_eq(u, rec.1 .0));
+            assert!(float_eq(v, rec.1 .1));
+        }
+    }
+
+    #[test]
+    fn a_truncated_cylinder_intersects_the_same_as_setting_the_fields() {
+        let t = Cylinder::truncated(1.0, 2.0, true);
+        let mut c = Cylinder::new();
+        c.minimum = 1.0;
+        c.maximum = 2.0;
+        c.closed = true;
+        let data = vec![
+            (Point::new(0.0, 3.0, 0.0), Vector::new(0.0, -1.0, 0.0)),
+            (Point::new(0.0, 3.0, -2.0), Vector::new(0.0, -1.0, 2.0)),
+            (Point::new(0.0, 1.5, -2.0), Vector::new(0.0, 0.0, 1.0)),
+            (Point::new(0.0, 3.0, -5.0), Vector::new(0.0, 0.0, 1.0)),
+        ];
+
+        for (origin, direction) in data {
+            let r = Ray::new(origin, direction.normalize());
+            let expected: Option<Vec<f64>> = c
+                .local_intersect(r)
+                .map(|xs| xs.iter().map(|x| x.t).collect());
+            let actual: Option<Vec<f64>> = t
+                .local_intersect(r)
+                .map(|xs| xs.iter().map(|x| x.t).collect());
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "minimum must not be greater than maximum")]
+    fn a_truncated_cylinder_with_swapped_bounds() {
+        Cylinder::truncated(2.0, 1.0, false);
+    }
+
+    #[test]
+    fn a_ray_hits_a_cylinder_with_a_larger_radius() {
+        let mut cyl = Cylinder::truncated(-1.0, 1.0, true);
+        let r = Ray::new(Point::new(1.5, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert!(cyl.local_intersect(r).is_none());
+
+        cyl.radius = 2.0;
+        let xs = cyl.local_intersect(r).unwrap();
+        let z = 1.75_f64.sqrt();
+
+        assert_eq!(xs.len(), 2);
+        assert!(float_eq(xs[0].t, 5.0 - z));
+        assert!(float_eq(xs[1].t, 5.0 + z));
+        assert_eq!(
+            cyl.local_normal_at(Point::new(1.5, 0.0, -z), None),
+            Vector::new(1.5, 0.0, -z)
+        );
+        assert_eq!(
+            cyl.local_normal_at(Point::new(1.5, 1.0, 0.0), None),
+            Vector::new(0.0, 1.0, 0.0)
+        );
+    }
 }
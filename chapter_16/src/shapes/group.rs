@@ -1,7 +1,8 @@
-use std::any::Any;
+use std::{any::Any, collections::HashMap, sync::OnceLock};
 
-use super::Shape;
-use crate::{Intersection, Material, Matrix, Point, Ray, Vector, IDENTITY};
+use super::{Shape, Triangle, CSG};
+use crate::id::new_id;
+use crate::{Bounds, Intersection, Intersections, Material, Matrix, Point, Ray, Vector, IDENTITY};
 use uuid::Uuid;
 
 #[derive(Debug)]
@@ -10,25 +11,114 @@ pub struct Group {
     parent_id: Option<Uuid>,
     pub transform: Matrix,
     pub material: Material,
-    pub objects: Vec<Box<dyn Shape>>,
+    objects: Vec<Box<dyn Shape>>,
     pub inherit_material: bool,
+    /// Name of the group, such as the name of a group in an OBJ file.
+    pub name: Option<String>,
+    // The bounds of the children, cleared whenever a child may have changed.
+    bounds: OnceLock<Bounds>,
 }
 
 impl Group {
     pub fn new() -> Group {
         Group {
-            id: Uuid::new_v4(),
+            id: new_id(),
             parent_id: None,
             transform: IDENTITY,
             material: Material::new(),
             objects: Vec::new(),
             inherit_material: false,
+            name: None,
+            bounds: OnceLock::new(),
         }
     }
 
     pub fn add_object(&mut self, mut shape: Box<dyn Shape>) {
         shape.set_parent_id(self.id);
         self.objects.push(shape);
+        self.bounds.take();
+    }
+
+    /// Add all of the `objects` to the group.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::shapes::{Group, Shape, Sphere};
+    ///
+    /// let mut g = Group::new();
+    /// g.add_objects(vec![Box::new(Sphere::new()), Box::new(Sphere::new())]);
+    ///
+    /// assert_eq!(g.objects().len(), 2);
+    /// ```
+    pub fn add_objects(&mut self, objects: Vec<Box<dyn Shape>>) {
+        self.objects.reserve(objects.len());
+        for mut shape in objects {
+            shape.set_parent_id(self.id);
+            self.objects.push(shape);
+        }
+        self.bounds.take();
+    }
+
+    /// Set the transformation of the group when building a new group.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{shapes::Group, Transformation};
+    ///
+    /// let m = Transformation::new().scale(2.0, 2.0, 2.0).build();
+    /// let g = Group::new().with_transform(m);
+    ///
+    /// assert_eq!(g.transform, m);
+    /// ```
+    pub fn with_transform(mut self, transform: Matrix) -> Group {
+        self.transform = transform;
+        self
+    }
+
+    /// The children of the group.
+    pub fn objects(&self) -> &[Box<dyn Shape>] {
+        &self.objects
+    }
+
+    /// Iterate over the children of the group as mutable. The bounds of the
+    /// group are found again the next time they are needed, as any of the
+    /// children may change. Children are added with `add_object` and removed
+    /// with `remove_object`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::shapes::{Group, Shape, Sphere};
+    /// use rustic_ray::Transformation;
+    ///
+    /// let mut g = Group::new();
+    /// g.add_object(Box::new(Sphere::new()));
+    /// for child in g.iter_mut() {
+    ///     child.set_transform(Transformation::new().translate(0.0, 3.0, 0.0).build());
+    /// }
+    ///
+    /// assert_eq!(g.bounds().max.y, 4.0);
+    /// ```
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut dyn Shape> + '_ {
+        self.bounds.take();
+        self.objects
+            .iter_mut()
+            .map(|o| o.as_mut() as &mut dyn Shape)
+    }
+
+    /// Remove the child at `index` from the group, returning the child or
+    /// `None` if there is no child at the `index`.
+    pub fn remove_object(&mut self, index: usize) -> Option<Box<dyn Shape>> {
+        if index >= self.objects.len() {
+            return None;
+        }
+
+        self.bounds.take();
+        let mut object = self.objects.remove(index);
+        object.clear_parent_id();
+        Some(object)
     }
 
     pub fn get_object(&self, index: usize) -> Option<&dyn Shape> {
@@ -37,6 +127,140 @@ impl Group {
             None => None,
         }
     }
+
+    /// Find the group with the `name` in the group or any of its child groups.
+    /// The children are searched before the groups nested inside of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::shapes::{Group, Shape, Sphere};
+    ///
+    /// let mut g = Group::new();
+    /// let mut head = Group::new();
+    /// head.name = Some("head".to_string());
+    /// head.add_object(Box::new(Sphere::new()));
+    /// let head_id = head.id();
+    /// g.add_object(Box::new(head));
+    ///
+    /// assert_eq!(g.get_object_by_name("head").unwrap().id(), head_id);
+    /// assert!(g.get_object_by_name("tail").is_none());
+    /// ```
+    pub fn get_object_by_name(&self, name: &str) -> Option<&dyn Shape> {
+        let groups = || {
+            self.objects
+                .iter()
+                .filter_map(|o| o.as_any().and_then(|a| a.downcast_ref::<Group>()))
+        };
+
+        match groups().find(|g| g.name.as_deref() == Some(name)) {
+            Some(g) => Some(g),
+            None => groups().find_map(|g| g.get_object_by_name(name)),
+        }
+    }
+
+    /// All of the shapes in the group that are not a group or [`CSG`]
+    /// themselves, the children of nested groups and [`CSG`]s are included
+    /// instead of the group or [`CSG`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::shapes::{Group, Shape, Sphere};
+    ///
+    /// let mut g1 = Group::new();
+    /// let mut g2 = Group::new();
+    /// g2.add_object(Box::new(Sphere::new()));
+    /// g1.add_object(Box::new(g2));
+    /// g1.add_object(Box::new(Sphere::new()));
+    ///
+    /// assert_eq!(g1.primitives().len(), 2);
+    /// ```
+    pub fn primitives(&self) -> Vec<&dyn Shape> {
+        let mut shapes = Vec::new();
+        for o in &self.objects {
+            Group::collect_primitives(o.as_ref(), &mut shapes);
+        }
+
+        shapes
+    }
+
+    /// Count of the shapes returned by `primitives`.
+    pub fn len_recursive(&self) -> usize {
+        self.primitives().len()
+    }
+
+    fn collect_primitives<'a>(shape: &'a dyn Shape, shapes: &mut Vec<&'a dyn Shape>) {
+        let any = shape.as_any();
+        if let Some(g) = any.and_then(|a| a.downcast_ref::<Group>()) {
+            for o in &g.objects {
+                Group::collect_primitives(o.as_ref(), shapes);
+            }
+        } else if let Some(c) = any.and_then(|a| a.downcast_ref::<CSG>()) {
+            Group::collect_primitives(c.left(), shapes);
+            Group::collect_primitives(c.right(), shapes);
+        } else {
+            shapes.push(shape);
+        }
+    }
+
+    /// Give every flat [`Triangle`] in the group, and in any child groups, a
+    /// normal at each vertex so they are rendered as smooth triangles. The
+    /// normal at a vertex is the average of the face normals of all of the
+    /// triangles sharing that vertex. Triangles that already have vertex
+    /// normals are left as they are.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{shapes::Group, shapes::Triangle, Point};
+    ///
+    /// let mut g = Group::new();
+    /// g.add_object(Box::new(Triangle::new(
+    ///     Point::new(0.0, 1.0, 0.0),
+    ///     Point::new(-1.0, 0.0, 0.0),
+    ///     Point::new(1.0, 0.0, 0.0),
+    /// )));
+    /// g.smooth_normals();
+    /// let t = g.get_object(0).unwrap().as_any().unwrap();
+    /// let t = t.downcast_ref::<Triangle>().unwrap();
+    ///
+    /// assert_eq!(t.n1, Some(t.face_normal()));
+    /// ```
+    pub fn smooth_normals(&mut self) {
+        let key = |p: Point| [p.x.to_bits(), p.y.to_bits(), p.z.to_bits()];
+        let mut normals: HashMap<[u64; 3], Vector> = HashMap::new();
+
+        self.for_each_triangle(&mut |t| {
+            for p in [t.p1, t.p2, t.p3] {
+                let n = normals.entry(key(p)).or_insert(Vector::new(0.0, 0.0, 0.0));
+                *n = *n + t.face_normal();
+            }
+        });
+
+        self.for_each_triangle(&mut |t| {
+            if t.n1.is_none() || t.n2.is_none() || t.n3.is_none() {
+                t.set_normals(
+                    normals[&key(t.p1)].normalize(),
+                    normals[&key(t.p2)].normalize(),
+                    normals[&key(t.p3)].normalize(),
+                );
+            }
+        });
+    }
+
+    // Call `f` for each triangle in the group and the groups nested within it.
+    fn for_each_triangle(&mut self, f: &mut dyn FnMut(&mut Triangle)) {
+        for o in self.objects.iter_mut() {
+            if let Some(any) = o.as_any_mut() {
+                if let Some(t) = any.downcast_mut::<Triangle>() {
+                    f(t);
+                } else if let Some(g) = any.downcast_mut::<Group>() {
+                    g.for_each_triangle(f);
+                }
+            }
+        }
+    }
 }
 
 impl Default for Group {
@@ -58,6 +282,10 @@ impl Shape for Group {
         self.parent_id = Some(id);
     }
 
+    fn clear_parent_id(&mut self) {
+        self.parent_id = None;
+    }
+
     fn shape_eq(&self, other: &dyn Shape) -> bool {
         self.id == other.id()
     }
@@ -98,6 +326,59 @@ impl Shape for Group {
         shape
     }
 
+    fn get_object_mut_by_id(&mut self, id: Uuid) -> Option<&mut dyn Shape> {
+        self.bounds.take();
+        for s in self.objects.iter_mut() {
+            if s.id() == id {
+                return Some(s.as_mut());
+            }
+            if s.contains_object_by_id(id) {
+                return s.get_object_mut_by_id(id);
+            }
+        }
+
+        None
+    }
+
+    fn remove_object_by_id(&mut self, id: Uuid) -> Option<Box<dyn Shape>> {
+        self.bounds.take();
+        if let Some(index) = self.objects.iter().position(|s| s.id() == id) {
+            let mut object = self.objects.remove(index);
+            object.clear_parent_id();
+            return Some(object);
+        }
+
+        for s in self.objects.iter_mut() {
+            if s.contains_object_by_id(id) {
+                return s.remove_object_by_id(id);
+            }
+        }
+
+        None
+    }
+
+    fn replace_object_by_id(
+        &mut self,
+        id: Uuid,
+        mut object: Box<dyn Shape>,
+    ) -> Option<Box<dyn Shape>> {
+        self.bounds.take();
+        if let Some(index) = self.objects.iter().position(|s| s.id() == id) {
+            object.set_parent_id(self.id);
+            let mut replaced = std::mem::replace(&mut self.objects[index], object);
+            replaced.clear_parent_id();
+            return Some(replaced);
+        }
+
+        for s in self.objects.iter_mut() {
+            if s.contains_object_by_id(id) {
+                return s.replace_object_by_id(id, object);
+            }
+        }
+
+        None
+    }
+
     fn contains_object_by_id(&self, id: Uuid) -> bool {
         let mut contains = false;
         for s in &self.objects {
@@ -115,22 +396,28 @@ impl Shape for Group {
     }
 
     fn local_intersect(&self, ray: Ray) -> Option<Vec<Intersection>> {
-        let mut xs: Vec<Intersection> = Vec::new();
-
-        for o in &self.objects {
-            if let Some(oxs) = o.intersect(ray) {
-                for ox in oxs {
-                    xs.push(ox);
-                }
-            }
+        if !self.bounds().intersects(ray) {
+            return None;
         }
 
-        if xs.is_empty() {
-            None
-        } else {
-            xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
-            Some(xs)
-        }
+        let xs: Intersections = self
+            .objects
+            .iter()
+            .filter_map(|o| o.intersect(ray))
+            .flatten()
+            .collect();
+
+        xs.into()
+    }
+
+    /// The box around the bounds of all of the children in the space of the
+    /// group. The box is only found once and kept until the children change.
+    fn bounds(&self) -> Bounds {
+        *self.bounds.get_or_init(|| {
+            self.objects
+                .iter()
+                .fold(Bounds::empty(), |b, o| b.merge(o.parent_space_bounds()))
+        })
     }
 
     fn local_normal_at(&self, _point: Point, _hit: Option<&Intersection>) -> Vector {
@@ -144,14 +431,20 @@ impl Shape for Group {
     fn as_any(&self) -> Option<&dyn Any> {
         Some(self)
     }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        self.bounds.take();
+        Some(self)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{
-        shapes::{Sphere, TestShape},
+        shapes::{CsgOperation, Cylinder, Sphere, TestShape},
         Transformation,
     };
+    use std::{f64::consts::PI, sync::atomic::Ordering};
 
     use super::*;
 
@@ -236,4 +529,255 @@ mod tests {
         let xs = g.intersect(r).unwrap();
         assert_eq!(xs.len(), 2);
     }
+
+    #[test]
+    fn smoothing_the_normals_of_triangles_sharing_an_edge() {
+        let mut g = Group::new();
+        g.add_object(Box::new(Triangle::new(
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(0.0, 1.0, 0.0),
+        )));
+        let mut child = Group::new();
+        child.add_object(Box::new(Triangle::new(
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(0.0, 0.0, -1.0),
+        )));
+        g.add_object(Box::new(child));
+
+        g.smooth_normals();
+
+        let t1 = g.get_object(0).unwrap().as_any().unwrap();
+        let t1 = t1.downcast_ref::<Triangle>().unwrap();
+        let child = g.get_object(1).unwrap().as_any().unwrap();
+        let child = child.downcast_ref::<Group>().unwrap();
+        let t2 = child.get_object(0).unwrap().as_any().unwrap();
+        let t2 = t2.downcast_ref::<Triangle>().unwrap();
+        let shared = Vector::new(1.0, 0.0, -1.0).normalize();
+
+        assert_eq!(t1.face_normal(), Vector::new(0.0, 0.0, -1.0));
+        assert_eq!(t2.face_normal(), Vector::new(1.0, 0.0, 0.0));
+        assert_eq!(t1.n1, Some(shared));
+        assert_eq!(t1.n2, Some(Vector::new(0.0, 0.0, -1.0)));
+        assert_eq!(t1.n3, Some(shared));
+        assert_eq!(t2.n1, Some(shared));
+        assert_eq!(t2.n2, Some(shared));
+        assert_eq!(t2.n3, Some(Vector::new(1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn smoothing_normals_makes_flat_triangles_smooth() {
+        let mut g = Group::new();
+        g.add_object(Box::new(Triangle::new(
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+        )));
+        let n = Vector::new(0.0, 1.0, 0.0);
+        g.add_object(Box::new(Triangle::smooth_triangle(
+            Point::new(5.0, 1.0, 0.0),
+            Point::new(4.0, 0.0, 0.0),
+            Point::new(6.0, 0.0, 0.0),
+            n,
+            n,
+            n,
+        )));
+
+        g.smooth_normals();
+
+        let t1 = g.get_object(0).unwrap();
+        let t2 = g.get_object(1).unwrap().as_any().unwrap();
+        let t2 = t2.downcast_ref::<Triangle>().unwrap();
+        let r = Ray::new(Point::new(-0.2, 0.3, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = t1.local_intersect(r).unwrap();
+
+        assert_eq!(
+            t1.local_normal_at(Point::new(-0.2, 0.3, 0.0), Some(&xs[0])),
+            Vector::new(0.0, 0.0, -1.0)
+        );
+        assert!(t1
+            .as_any()
+            .unwrap()
+            .downcast_ref::<Triangle>()
+            .unwrap()
+            .n1
+            .is_some());
+        assert_eq!((t2.n1, t2.n2, t2.n3), (Some(n), Some(n), Some(n)));
+    }
+
+    #[test]
+    fn the_primitives_of_nested_groups() {
+        let mut g1 = Group::new();
+        g1.transform = Transformation::new().rotate_y(PI / 2.0).build();
+
+        let mut g2 = Group::new();
+        g2.transform = Transformation::new().scale(2.0, 2.0, 2.0).build();
+
+        let mut s = Sphere::new();
+        let s_id = s.id();
+        s.transform = Transformation::new().translate(5.0, 0.0, 0.0).build();
+
+        g2.add_object(Box::new(s));
+        g1.add_object(Box::new(g2));
+
+        assert_eq!(g1.len_recursive(), 1);
+        assert_eq!(g1.primitives()[0].id(), s_id);
+    }
+
+    #[test]
+    fn the_primitives_of_a_group_include_the_children_of_csg() {
+        let s1 = Sphere::new();
+        let s2 = Sphere::new();
+        let s3 = Sphere::new();
+        let ids = vec![s1.id(), s2.id(), s3.id()];
+        let mut g = Group::new();
+        g.add_object(Box::new(s1));
+        g.add_object(Box::new(CSG::new(
+            CsgOperation::Union,
+            Box::new(s2),
+            Box::new(s3),
+        )));
+        g.add_object(Box::new(Group::new()));
+
+        let primitives: Vec<Uuid> = g.primitives().iter().map(|s| s.id()).collect();
+
+        assert_eq!(g.len_recursive(), 3);
+        assert_eq!(primitives, ids);
+    }
+
+    #[test]
+    fn adding_several_children_to_a_group() {
+        let m = Transformation::new().translate(1.0, 0.0, 0.0).build();
+        let mut g = Group::new().with_transform(m);
+        let shapes: Vec<Box<dyn Shape>> = vec![
+            Box::new(Sphere::new()),
+            Box::new(Sphere::new()),
+            Box::new(Sphere::new()),
+        ];
+        let ids: Vec<Uuid> = shapes.iter().map(|s| s.id()).collect();
+        g.add_objects(shapes);
+
+        assert_eq!(g.transform, m);
+        assert_eq!(g.objects.len(), 3);
+        for (object, id) in g.objects.iter().zip(ids) {
+            assert_eq!(object.id(), id);
+            assert_eq!(object.parent_id(), Some(g.id()));
+        }
+    }
+
+    #[test]
+    fn a_group_has_a_bounding_box_that_contains_its_children() {
+        let mut s = Sphere::new();
+        s.transform = Transformation::new()
+            .scale(2.0, 2.0, 2.0)
+            .translate(2.0, 5.0, -3.0)
+            .build();
+        let mut c = Cylinder::truncated(-2.0, 2.0, false);
+        c.transform = Transformation::new()
+            .scale(0.5, 1.0, 0.5)
+            .translate(-4.0, -1.0, 4.0)
+            .build();
+        let mut g = Group::new();
+        g.add_object(Box::new(s));
+        g.add_object(Box::new(c));
+        let b = g.bounds();
+
+        assert_eq!(b.min, Point::new(-4.5, -3.0, -5.0));
+        assert_eq!(b.max, Point::new(4.0, 7.0, 4.5));
+        assert!(Group::new().bounds().is_empty());
+    }
+
+    #[test]
+    fn adding_a_shape_grows_the_cached_bounds_of_a_group() {
+        let mut g = Group::new();
+        g.add_object(Box::new(Sphere::new()));
+        assert_eq!(g.bounds().max, Point::new(1.0, 1.0, 1.0));
+
+        let mut s = Sphere::new();
+        s.transform = Transformation::new().translate(5.0, 0.0, 0.0).build();
+        g.add_object(Box::new(s));
+        let b = g.bounds();
+
+        assert_eq!(b.min, Point::new(-1.0, -1.0, -1.0));
+        assert_eq!(b.max, Point::new(6.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn the_bounds_of_a_group_are_only_found_once() {
+        let mut g = Group::new();
+        g.add_object(Box::new(TestShape::new()));
+        let calls = |g: &Group| {
+            g.objects[0]
+                .as_any()
+                .and_then(|a| a.downcast_ref::<TestShape>())
+                .unwrap()
+                .bounds_calls
+                .load(Ordering::Relaxed)
+        };
+
+        for _ in 0..3 {
+            g.bounds();
+            g.intersect(Ray::new(
+                Point::new(0.0, 0.0, -5.0),
+                Vector::new(0.0, 0.0, 1.0),
+            ));
+        }
+        assert_eq!(calls(&g), 1);
+
+        let id = g.objects[0].id();
+        g.get_object_mut_by_id(id)
+            .unwrap()
+            .set_transform(Transformation::new().translate(0.0, 3.0, 0.0).build());
+        assert_eq!(g.bounds().max, Point::new(1.0, 4.0, 1.0));
+        assert_eq!(calls(&g), 2);
+    }
+
+    #[test]
+    fn a_ray_missing_the_bounds_of_a_group_does_not_test_the_children() {
+        let mut g = Group::new();
+        g.add_object(Box::new(TestShape::new()));
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 1.0, 0.0));
+        g.intersect(r);
+
+        let child = g.objects[0]
+            .as_any()
+            .and_then(|a| a.downcast_ref::<TestShape>())
+            .unwrap();
+        assert!(child.saved_ray.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn moving_a_child_through_iter_mut_updates_the_bounds() {
+        let mut g = Group::new();
+        g.add_object(Box::new(Sphere::new()));
+        let r = Ray::new(Point::new(5.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert!(g.intersect(r).is_none());
+
+        for child in g.iter_mut() {
+            child.set_transform(Transformation::new().translate(5.0, 0.0, 0.0).build());
+        }
+
+        assert_eq!(g.bounds().max, Point::new(6.0, 1.0, 1.0));
+        assert_eq!(g.intersect(r).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn removing_a_child_updates_the_bounds_and_clears_its_parent() {
+        let mut g = Group::new();
+        g.add_object(Box::new(Sphere::new()));
+        let mut s = Sphere::new();
+        s.transform = Transformation::new().translate(5.0, 0.0, 0.0).build();
+        g.add_object(Box::new(s));
+
+        assert_eq!(g.bounds().max, Point::new(6.0, 1.0, 1.0));
+
+        let removed = g.remove_object(1).unwrap();
+
+        assert!(removed.parent_id().is_none());
+        assert_eq!(g.objects().len(), 1);
+        assert_eq!(g.bounds().max, Point::new(1.0, 1.0, 1.0));
+        assert!(g.remove_object(1).is_none());
+    }
 }
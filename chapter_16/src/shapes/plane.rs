@@ -1,13 +1,25 @@
+use std::any::Any;
+
 use super::Shape;
+use crate::id::new_id;
 #[allow(unused_imports)]
 use crate::Transformation;
-use crate::{Intersection, Material, Matrix, Point, Ray, Vector, EPSILON, IDENTITY};
+use crate::{Bounds, Intersection, Material, Matrix, Point, Ray, Vector, EPSILON, IDENTITY};
 use uuid::Uuid;
 
 /// A perfectly flat surface that extends infinitely in two dimensions.
 ///
 /// The place extends infinitely far in both teh `x` and `z` dimensions passing
 /// through the origin.
+///
+/// A plane can be limited to a rectangle with [`Plane::with_bounds`], it is
+/// infinite by default.
+///
+/// A plane facing another direction, such as a wall, can be created with
+/// [`Plane::oriented`] instead of rotating it, which would rotate its pattern
+/// as well. The limits of a plane facing mostly along `z` are along the `x`
+/// and `y` axes instead of `x` and `z`, and of one facing mostly along `x`
+/// along the `z` and `y` axes, the same axes as its texture coordinates.
 #[derive(Debug)]
 pub struct Plane {
     id: Uuid,
@@ -16,16 +28,95 @@ pub struct Plane {
     pub transform: Matrix,
     /// [`Material`] describing the look of the `Plane`
     pub material: Material,
+    /// Minimum extent along the x-axis defined in object space
+    pub min_x: f64,
+    /// Maximum extent along the x-axis defined in object space
+    pub max_x: f64,
+    /// Minimum extent along the z-axis defined in object space
+    pub min_z: f64,
+    /// Maximum extent along the z-axis defined in object space
+    pub max_z: f64,
+    /// Direction the plane faces in object space, `+y` by default
+    pub normal: Vector,
 }
 
 impl Plane {
     /// Create a new plane.
     pub fn new() -> Self {
         Plane {
-            id: Uuid::new_v4(),
+            id: new_id(),
             parent_id: None,
             transform: IDENTITY,
             material: Material::new(),
+            min_x: f64::NEG_INFINITY,
+            max_x: f64::INFINITY,
+            min_z: f64::NEG_INFINITY,
+            max_z: f64::INFINITY,
+            normal: Vector::new(0.0, 1.0, 0.0),
+        }
+    }
+
+    /// Create a new plane through the origin facing the direction of `normal`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `normal` has no direction, a length of `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{shapes::Plane, shapes::Shape, Point, Ray, Vector};
+    ///
+    /// let wall = Plane::oriented(Vector::new(0.0, 0.0, 1.0));
+    /// let r = Ray::new(Point::new(0.0, 0.0, 2.0), Vector::new(0.0, 0.0, -1.0));
+    ///
+    /// assert_eq!(wall.intersect(r).unwrap()[0].t, 2.0);
+    /// ```
+    pub fn oriented(normal: Vector) -> Plane {
+        assert!(
+            normal.magnitude() > 0.0,
+            "the normal of a plane must have a direction"
+        );
+        Plane {
+            normal: normal.normalize(),
+            ..Plane::new()
+        }
+    }
+
+    /// Create a new plane limited to the rectangle from `min_x` to `max_x`
+    /// along the x-axis and `min_z` to `max_z` along the z-axis.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{shapes::Plane, shapes::Shape, Point, Ray, Vector};
+    ///
+    /// let p = Plane::with_bounds(-1.0, 1.0, -2.0, 2.0);
+    /// let r = Ray::new(Point::new(3.0, 1.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+    ///
+    /// assert!(p.intersect(r).is_none());
+    /// ```
+    pub fn with_bounds(min_x: f64, max_x: f64, min_z: f64, max_z: f64) -> Plane {
+        Plane {
+            min_x,
+            max_x,
+            min_z,
+            max_z,
+            ..Plane::new()
+        }
+    }
+
+    // The indices of the two axes across the plane, the first limited by
+    // `min_x` and `max_x` and the second by `min_z` and `max_z`, followed by
+    // the axis the plane faces along the most.
+    fn axes(&self) -> (usize, usize, usize) {
+        let n = self.normal;
+        if n.y.abs() >= n.x.abs() && n.y.abs() >= n.z.abs() {
+            (0, 2, 1)
+        } else if n.z.abs() >= n.x.abs() {
+            (0, 1, 2)
+        } else {
+            (2, 1, 0)
         }
     }
 }
@@ -49,6 +140,10 @@ impl Shape for Plane {
         self.parent_id = Some(id);
     }
 
+    fn clear_parent_id(&mut self) {
+        self.parent_id = None;
+    }
+
     fn transform(&self) -> Matrix {
         self.transform
     }
@@ -70,16 +165,67 @@ impl Shape for Plane {
     }
 
     fn local_intersect(&self, ray: Ray) -> Option<Vec<Intersection>> {
-        if ray.direction.y.abs() < EPSILON {
+        let denominator = ray.direction.dot(self.normal);
+        if denominator.abs() < EPSILON {
+            return None;
+        }
+
+        let t = -(ray.origin - Point::new(0.0, 0.0, 0.0)).dot(self.normal) / denominator;
+        let (u, v, _) = self.axes();
+        let p = ray.position(t).as_array();
+        if p[u] < self.min_x || p[u] > self.max_x || p[v] < self.min_z || p[v] > self.max_z {
             return None;
         }
 
-        let t = -ray.origin.y / ray.direction.y;
         Some(vec![Intersection::new(t, self)])
     }
 
+    fn bounds(&self) -> Bounds {
+        let (u, v, w) = self.axes();
+        let n = self.normal.as_array();
+        let corner = |a: f64, b: f64| {
+            let mut p = [0.0; 3];
+            p[u] = a;
+            p[v] = b;
+            // a tilted plane rises along the axis it faces as it crosses the
+            // other two
+            if n[u] != 0.0 || n[v] != 0.0 {
+                p[w] = -(n[u] * a + n[v] * b) / n[w];
+            }
+            Point::from(p)
+        };
+
+        let limits = [self.min_x, self.max_x, self.min_z, self.max_z];
+        if (n[u] != 0.0 || n[v] != 0.0) && limits.iter().any(|l| l.is_infinite()) {
+            return Bounds::infinite();
+        }
+
+        Bounds::empty()
+            .add_point(corner(self.min_x, self.min_z))
+            .add_point(corner(self.min_x, self.max_z))
+            .add_point(corner(self.max_x, self.min_z))
+            .add_point(corner(self.max_x, self.max_z))
+    }
+
     fn local_normal_at(&self, _point: Point, _hit: Option<&Intersection>) -> Vector {
-        Vector::new(0.0, 1.0, 0.0)
+        self.normal
+    }
+
+    /// Planar mapping, `u` and `v` repeat every unit along the `x` and `z`
+    /// axes. A plane facing mostly along `z` uses the `x` and `y` axes
+    /// instead and one facing mostly along `x` the `z` and `y` axes.
+    fn uv_at(&self, point: Point) -> (f64, f64) {
+        let (u, v, _) = self.axes();
+        let p = point.as_array();
+        (p[u].rem_euclid(1.0), p[v].rem_euclid(1.0))
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
     }
 }
 
@@ -148,4 +294,133 @@ mod tests {
         assert_eq!(xs[0].t, 1.0);
         assert!(p.shape_eq(xs[0].object));
     }
+
+    // Bonus Chapter Texture Mapping
+    #[test]
+    fn using_a_planar_mapping_on_a_3d_point() {
+        let p = Plane::new();
+        let data = vec![
+            (Point::new(0.25, 0.0, 0.5), (0.25, 0.5)),
+            (Point::new(0.25, 0.0, -0.25), (0.25, 0.75)),
+            (Point::new(0.25, 0.5, -0.25), (0.25, 0.75)),
+            (Point::new(1.25, 0.0, 0.5), (0.25, 0.5)),
+            (Point::new(0.25, 0.0, -1.75), (0.25, 0.25)),
+            (Point::new(1.0, 0.0, -1.0), (0.0, 0.0)),
+            (Point::new(0.0, 0.0, 0.0), (0.0, 0.0)),
+        ];
+
+        for rec in data {
+            assert_eq!(p.uv_at(rec.0), rec.1);
+        }
+    }
+
+    #[test]
+    fn a_ray_intersecting_a_bounded_plane_inside_the_bounds() {
+        let p = Plane::with_bounds(-1.0, 1.0, -2.0, 2.0);
+        let r = Ray::new(Point::new(0.5, 1.0, -1.5), Vector::new(0.0, -1.0, 0.0));
+        let xs = p.local_intersect(r).expect("No intersections");
+
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 1.0);
+    }
+
+    #[test]
+    fn a_ray_missing_a_bounded_plane_outside_the_bounds() {
+        let p = Plane::with_bounds(-1.0, 1.0, -2.0, 2.0);
+        let data = vec![
+            Point::new(1.5, 1.0, 0.0),
+            Point::new(-1.5, 1.0, 0.0),
+            Point::new(0.0, 1.0, 2.5),
+            Point::new(0.0, 1.0, -2.5),
+        ];
+
+        for origin in data {
+            let r = Ray::new(origin, Vector::new(0.0, -1.0, 0.0));
+            assert_eq!(p.local_intersect(r), None);
+        }
+    }
+
+    #[test]
+    fn the_default_plane_is_unbounded() {
+        let p = Plane::new();
+        let r = Ray::new(Point::new(1e6, 1.0, -1e6), Vector::new(0.0, -1.0, 0.0));
+
+        assert_eq!(p.min_x, f64::NEG_INFINITY);
+        assert_eq!(p.max_z, f64::INFINITY);
+        assert!(p.local_intersect(r).is_some());
+    }
+
+    #[test]
+    fn a_bounded_plane_has_a_flat_bounding_box() {
+        let p = Plane::with_bounds(-1.0, 2.0, -3.0, 4.0);
+        let b = p.bounds();
+
+        assert_eq!(b.min, Point::new(-1.0, 0.0, -3.0));
+        assert_eq!(b.max, Point::new(2.0, 0.0, 4.0));
+        assert!(Plane::new().bounds().is_infinite());
+    }
+
+    #[test]
+    fn a_ray_along_negative_z_hits_a_plane_facing_positive_z() {
+        let p = Plane::oriented(Vector::new(0.0, 0.0, 1.0));
+        let r = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::new(0.0, 0.0, -1.0));
+        let xs = p.local_intersect(r).expect("No intersections");
+
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 3.0);
+        assert_eq!(
+            p.local_normal_at(r.position(xs[0].t), None),
+            Vector::new(0.0, 0.0, 1.0)
+        );
+        assert!(p
+            .local_intersect(Ray::new(
+                Point::new(0.0, 0.0, 1.0),
+                Vector::new(0.0, 1.0, 0.0)
+            ))
+            .is_none());
+        assert_eq!(p.uv_at(Point::new(0.25, 0.5, 0.0)), (0.25, 0.5));
+        assert!(p.bounds().max.y.is_infinite());
+    }
+
+    #[test]
+    fn a_wall_is_limited_along_its_own_axes() {
+        let mut p = Plane::oriented(Vector::new(0.0, 0.0, 1.0));
+        p.min_x = -1.0;
+        p.max_x = 1.0;
+        p.min_z = 0.0;
+        p.max_z = 2.0;
+        let hits = |x: f64, y: f64| {
+            let r = Ray::new(Point::new(x, y, 3.0), Vector::new(0.0, 0.0, -1.0));
+            p.local_intersect(r).is_some()
+        };
+
+        assert!(hits(0.5, 1.0));
+        assert!(!hits(1.5, 1.0));
+        assert!(!hits(0.5, -0.5));
+        assert!(!hits(0.5, 2.5));
+        assert_eq!(p.bounds().min, Point::new(-1.0, 0.0, 0.0));
+        assert_eq!(p.bounds().max, Point::new(1.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn a_bounded_tilted_plane_has_a_finite_bounding_box() {
+        let mut p = Plane::oriented(Vector::new(0.0, 1.0, 1.0));
+        p.min_x = -1.0;
+        p.max_x = 1.0;
+        p.min_z = -2.0;
+        p.max_z = 2.0;
+        let b = p.bounds();
+
+        assert_eq!(b.min, Point::new(-1.0, -2.0, -2.0));
+        assert_eq!(b.max, Point::new(1.0, 2.0, 2.0));
+        assert!(Plane::oriented(Vector::new(0.0, 1.0, 1.0))
+            .bounds()
+            .is_infinite());
+    }
+
+    #[test]
+    #[should_panic]
+    fn a_plane_facing_no_direction_panics() {
+        Plane::oriented(Vector::new(0.0, 0.0, 0.0));
+    }
 }
@@ -0,0 +1,296 @@
+use std::any::Any;
+
+use super::Shape;
+use crate::id::new_id;
+use crate::{Bounds, Intersection, Material, Matrix, Point, Ray, Vector, EPSILON, IDENTITY};
+use uuid::Uuid;
+
+/// A flat shape with any number of corners, all lying in the same plane. The
+/// polygon does not have to be convex, a ray is tested against the outline of
+/// the polygon so a notch cut into a face is left open.
+#[derive(Debug)]
+pub struct Polygon {
+    id: Uuid,
+    parent_id: Option<Uuid>,
+    pub transform: Matrix,
+    pub material: Material,
+    vertices: Vec<Point>,
+    normal: Vector,
+}
+
+impl Polygon {
+    /// Create a polygon with the corners `vertices` in order around the
+    /// outline. The normal follows the same winding as a [`Triangle`](super::Triangle).
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are less than three `vertices` or if the `vertices`
+    /// all lie on one line and so do not span a plane.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{shapes::Polygon, shapes::Shape, Point, Ray, Vector};
+    ///
+    /// let p = Polygon::new(vec![
+    ///     Point::new(0.0, 4.0, 0.0),
+    ///     Point::new(0.0, 0.0, 0.0),
+    ///     Point::new(4.0, 0.0, 0.0),
+    ///     Point::new(4.0, 4.0, 0.0),
+    ///     Point::new(2.0, 1.0, 0.0),
+    /// ]);
+    /// let notch = Ray::new(Point::new(2.0, 3.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+    /// let corner = Ray::new(Point::new(1.0, 1.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+    ///
+    /// assert!(p.intersect(notch).is_none());
+    /// assert_eq!(p.intersect(corner).unwrap()[0].t, 5.0);
+    /// ```
+    pub fn new(vertices: Vec<Point>) -> Polygon {
+        assert!(
+            vertices.len() >= 3,
+            "a polygon needs at least three vertices"
+        );
+
+        // The sum of the normals of a fan of triangles, the area of each
+        // triangle weights its normal so the sum also holds for concave
+        // polygons.
+        let p1 = vertices[0];
+        let normal = vertices[1..]
+            .windows(2)
+            .fold(Vector::new(0.0, 0.0, 0.0), |n, w| {
+                n + (w[1] - p1).cross(w[0] - p1)
+            });
+        assert!(
+            normal.magnitude() >= EPSILON,
+            "the vertices of a polygon must not lie on one line"
+        );
+        let normal = normal.normalize();
+
+        Polygon {
+            id: new_id(),
+            parent_id: None,
+            transform: IDENTITY,
+            material: Material::new(),
+            vertices,
+            normal,
+        }
+    }
+
+    /// The corners of the polygon in order around the outline.
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    // Check if the `point` in the plane of the polygon is inside of the
+    // outline. The polygon is flattened by dropping the axis the normal points
+    // most along, then a line from the point is checked for how many edges it
+    // crosses, an odd number of crossings is inside.
+    fn contains_point(&self, point: Point) -> bool {
+        let (nx, ny, nz) = (
+            self.normal.x.abs(),
+            self.normal.y.abs(),
+            self.normal.z.abs(),
+        );
+        let flatten = |p: Point| {
+            if nx >= ny && nx >= nz {
+                (p.y, p.z)
+            } else if ny >= nz {
+                (p.x, p.z)
+            } else {
+                (p.x, p.y)
+            }
+        };
+
+        let (px, py) = flatten(point);
+        let mut inside = false;
+        let mut j = self.vertices.len() - 1;
+        for i in 0..self.vertices.len() {
+            let (xi, yi) = flatten(self.vertices[i]);
+            let (xj, yj) = flatten(self.vertices[j]);
+            if (yi > py) != (yj > py) && px < (xj - xi) * (py - yi) / (yj - yi) + xi {
+                inside = !inside;
+            }
+            j = i;
+        }
+
+        inside
+    }
+}
+
+impl Shape for Polygon {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+
+    fn parent_id(&self) -> Option<Uuid> {
+        self.parent_id
+    }
+
+    fn set_parent_id(&mut self, id: Uuid) {
+        self.parent_id = Some(id);
+    }
+
+    fn clear_parent_id(&mut self) {
+        self.parent_id = None;
+    }
+
+    fn transform(&self) -> Matrix {
+        self.transform
+    }
+
+    fn set_transform(&mut self, transform: Matrix) {
+        self.transform = transform;
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn material_mut(&mut self) -> &mut Material {
+        &mut self.material
+    }
+
+    fn set_material(&mut self, material: Material) {
+        self.material = material;
+    }
+
+    fn local_intersect(&self, ray: Ray) -> Option<Vec<Intersection<'_>>> {
+        let denom = self.normal.dot(ray.direction);
+        if denom.abs() < EPSILON {
+            return None;
+        }
+
+        let t = self.normal.dot(self.vertices[0] - ray.origin) / denom;
+        if !self.contains_point(ray.position(t)) {
+            return None;
+        }
+
+        let mut i = Intersection::new(t, self);
+        i.backface = denom > 0.0;
+        Some(vec![i])
+    }
+
+    fn bounds(&self) -> Bounds {
+        self.vertices
+            .iter()
+            .fold(Bounds::empty(), |b, p| b.add_point(*p))
+    }
+
+    fn local_normal_at(&self, _point: Point, _hit: Option<&Intersection>) -> Vector {
+        self.normal
+    }
+
+    fn inherit_material(&self) -> bool {
+        true
+    }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::Triangle;
+
+    fn concave_pentagon() -> Vec<Point> {
+        vec![
+            Point::new(0.0, 4.0, 0.0),
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(4.0, 0.0, 0.0),
+            Point::new(4.0, 4.0, 0.0),
+            Point::new(2.0, 1.0, 0.0),
+        ]
+    }
+
+    #[test]
+    fn a_polygon_with_three_vertices_has_the_normal_of_a_triangle() {
+        let p1 = Point::new(0.0, 1.0, 0.0);
+        let p2 = Point::new(-1.0, 0.0, 0.0);
+        let p3 = Point::new(1.0, 0.0, 0.0);
+        let p = Polygon::new(vec![p1, p2, p3]);
+        let t = Triangle::new(p1, p2, p3);
+
+        assert_eq!(
+            p.local_normal_at(Point::new(0.0, 0.5, 0.0), None),
+            t.face_normal()
+        );
+    }
+
+    #[test]
+    fn a_ray_through_the_notch_of_a_concave_polygon_misses() {
+        let p = Polygon::new(concave_pentagon());
+        let r = Ray::new(Point::new(2.0, 3.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert!(p.local_intersect(r).is_none());
+
+        // A fan of triangles from the first vertex covers the notch.
+        let v = concave_pentagon();
+        let fan_hits = (1..v.len() - 1)
+            .filter(|&i| {
+                Triangle::new(v[0], v[i], v[i + 1])
+                    .local_intersect(r)
+                    .is_some()
+            })
+            .count();
+        assert!(fan_hits > 0);
+    }
+
+    #[test]
+    fn a_ray_strikes_a_concave_polygon() {
+        let p = Polygon::new(concave_pentagon());
+        let data = vec![
+            Point::new(1.0, 1.0, -5.0),
+            Point::new(3.5, 3.0, -5.0),
+            Point::new(0.25, 3.5, -5.0),
+        ];
+
+        for origin in data {
+            let r = Ray::new(origin, Vector::new(0.0, 0.0, 1.0));
+            let xs = p.local_intersect(r).unwrap();
+
+            assert_eq!(xs.len(), 1);
+            assert_eq!(xs[0].t, 5.0);
+        }
+    }
+
+    #[test]
+    fn a_ray_parallel_to_a_polygon_misses() {
+        let p = Polygon::new(concave_pentagon());
+        let r = Ray::new(Point::new(1.0, 1.0, -5.0), Vector::new(0.0, 1.0, 0.0));
+
+        assert!(p.local_intersect(r).is_none());
+    }
+
+    #[test]
+    fn a_polygon_has_a_bounding_box() {
+        let p = Polygon::new(concave_pentagon());
+        let b = p.bounds();
+
+        assert_eq!(b.min, Point::new(0.0, 0.0, 0.0));
+        assert_eq!(b.max, Point::new(4.0, 4.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn a_polygon_with_collinear_vertices_panics() {
+        Polygon::new(vec![
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(2.0, 0.0, 0.0),
+            Point::new(3.0, 0.0, 0.0),
+        ]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn a_polygon_with_repeated_vertices_panics() {
+        let p = Point::new(1.0, 2.0, 3.0);
+        Polygon::new(vec![p, p, p]);
+    }
+}
@@ -1,15 +1,19 @@
-use crate::{Intersection, Material, Matrix, Point, Ray, Vector, World};
+use crate::{Bounds, Intersection, Material, Matrix, Point, Ray, Vector, World};
 use std::{any::Any, fmt};
 use uuid::Uuid;
 
 /// Trait with common functionality for types that describe an object or
 /// a graphical primitive. Abstraction of the implementation for a particular
 /// shape.
-pub trait Shape: Any + fmt::Debug {
+pub trait Shape: Any + fmt::Debug + Send + Sync {
     fn as_any(&self) -> Option<&dyn Any> {
         None
     }
 
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        None
+    }
+
     /// Get the unique identifier for an object.
     fn id(&self) -> Uuid;
 
@@ -19,6 +23,9 @@ pub trait Shape: Any + fmt::Debug {
     /// Set parent id of an `object`
     fn set_parent_id(&mut self, id: Uuid);
 
+    /// Clear the parent id of an `object` taken out of its parent
+    fn clear_parent_id(&mut self);
+
     /// Test if `other` is equal to `self` by comparing their `id`'s.
     fn shape_eq(&self, other: &dyn Shape) -> bool {
         self.id() == other.id()
@@ -29,6 +36,26 @@ pub trait Shape: Any + fmt::Debug {
         None
     }
 
+    /// If the object is a container then get child with `id` as mutable.
+    fn get_object_mut_by_id(&mut self, _id: Uuid) -> Option<&mut dyn Shape> {
+        None
+    }
+
+    /// If the object is a container then remove the child with `id`.
+    fn remove_object_by_id(&mut self, _id: Uuid) -> Option<Box<dyn Shape>> {
+        None
+    }
+
+    /// If the object is a container then replace the child with `id` with
+    /// `object`, returning the child that was replaced.
+    fn replace_object_by_id(
+        &mut self,
+        _id: Uuid,
+        _object: Box<dyn Shape>,
+    ) -> Option<Box<dyn Shape>> {
+        None
+    }
+
     fn contains_object_by_id(&self, _id: Uuid) -> bool {
         false
     }
@@ -49,6 +76,13 @@ pub trait Shape: Any + fmt::Debug {
         false
     }
 
+    /// Gets the [`Material`] at a `point` in object space, for a shape with a
+    /// material that changes across its surface such as wet patches on a
+    /// floor. Defaults to `material()` everywhere.
+    fn material_at(&self, _point: Point) -> &Material {
+        self.material()
+    }
+
     /// Sets the [`Material`] as mutable for an object
     fn set_material(&mut self, material: Material);
 
@@ -62,6 +96,35 @@ pub trait Shape: Any + fmt::Debug {
     /// Calculate a vector that points perpendicular to a surface at a give point
     fn local_normal_at(&self, point: Point, hit: Option<&Intersection>) -> Vector;
 
+    /// Map a `point` in object space on the surface of an object to a two
+    /// dimensional `(u, v)` texture coordinate, each between `0.0` and `1.0`.
+    /// The default is a planar mapping, `u` and `v` repeat every unit along
+    /// the `x` and `z` axes.
+    fn uv_at(&self, point: Point) -> (f64, f64) {
+        (point.x.rem_euclid(1.0), point.z.rem_euclid(1.0))
+    }
+
+    /// Map a `point` in object space to a `(u, v)` texture coordinate the same
+    /// as `uv_at`, shapes that record the `u` and `v` of an intersection use
+    /// the `hit` instead.
+    fn uv_at_hit(&self, point: Point, _hit: Option<&Intersection>) -> (f64, f64) {
+        self.uv_at(point)
+    }
+
+    /// The box in object space containing the shape, used to skip
+    /// intersecting the shape with a [`Ray`] that misses the box. Defaults to
+    /// an infinite box which never skips the shape.
+    fn bounds(&self) -> Bounds {
+        Bounds::infinite()
+    }
+
+    /// The box containing the shape in the space of its parent, the `bounds`
+    /// transformed by the transformation of the shape. The parent of a shape
+    /// not in a group is the world.
+    fn parent_space_bounds(&self) -> Bounds {
+        self.bounds().transform(self.transform())
+    }
+
     /// Coverts the `ray` form world space into local space then calls
     /// the `local_intersect` implementation of an object, `self` to determine
     /// if the `ray` intersects with the object.
@@ -80,6 +143,9 @@ pub trait Shape: Any + fmt::Debug {
     /// assert_eq!(xs[1].t, 6.0,);
     /// ```
     fn intersect(&self, ray: Ray) -> Option<Vec<Intersection>> {
+        #[cfg(feature = "stats")]
+        crate::stats::record_intersection(self.id());
+
         let local_ray = ray.transform(self.transform().inverse());
         self.local_intersect(local_ray)
     }
@@ -112,7 +178,7 @@ pub trait Shape: Any + fmt::Debug {
             None => {
                 let local_point = self.transform().inverse() * point;
                 let local_normal = self.local_normal_at(local_point, i);
-                (self.transform().inverse().transpose() * local_normal).normalize()
+                (self.transform().inverse_transpose() * local_normal).normalize()
             }
         }
     }
@@ -130,7 +196,7 @@ pub trait Shape: Any + fmt::Debug {
     }
 
     fn normal_to_world(&self, normal: Vector, w: &World) -> Vector {
-        let world_normal = (self.transform().inverse().transpose() * normal).normalize();
+        let world_normal = (self.transform().inverse_transpose() * normal).normalize();
 
         match self.parent_id() {
             Some(id) => {
@@ -148,6 +214,27 @@ impl PartialEq for dyn Shape {
     }
 }
 
+/// Builder style helpers for any [`Shape`].
+pub trait ShapeExt: Shape + Sized {
+    /// Replace the [`Material`] of the shape, returning the shape.
+    ///
+    /// # Example
+    /// ```
+    /// use rustic_ray::shapes::{Cube, Shape, ShapeExt};
+    /// use rustic_ray::Material;
+    ///
+    /// let c = Cube::new().with_material(Material::glass());
+    ///
+    /// assert_eq!(c.material().transparency, 1.0);
+    /// ```
+    fn with_material(mut self, material: Material) -> Self {
+        self.set_material(material);
+        self
+    }
+}
+
+impl<T: Shape> ShapeExt for T {}
+
 #[cfg(test)]
 mod tests {
     use std::f64::consts::PI;
@@ -155,7 +242,7 @@ mod tests {
     use super::*;
     use crate::{
         shapes::Group,
-        shapes::{Sphere, TestShape},
+        shapes::{Cube, Plane, Sphere, TestShape},
         Transformation, IDENTITY,
     };
 
@@ -355,4 +442,25 @@ mod tests {
 
         assert_eq!(p, Vector::new(0.2857, 0.4286, -0.8571));
     }
+
+    #[test]
+    fn the_extension_sets_the_material_of_a_shape() {
+        let m = Material {
+            ambient: 1.0,
+            ..Material::new()
+        };
+        let s = Sphere::new().with_material(m.clone());
+
+        assert_eq!(s.material, m);
+    }
+
+    #[test]
+    fn a_glass_cube_is_transparent() {
+        let c = Cube::new().with_material(Material::glass());
+        let p = Plane::new().with_material(Material::mirror());
+
+        assert_eq!(c.material().transparency, 1.0);
+        assert_eq!(c.material().refractive_index, 1.5);
+        assert_eq!(p.material().reflective, 1.0);
+    }
 }
@@ -114,4 +114,37 @@ mod tests {
 
         assert_eq!(comps.normalv, Vector::new(-0.5547, 0.83205, 0.0));
     }
+
+    #[test]
+    fn shading_a_smooth_triangle_many_times_gives_the_same_normal() {
+        let tri = Triangle::smooth_triangle(
+            Background::P1,
+            Background::P2,
+            Background::P3,
+            Background::N1,
+            Background::N2,
+            Background::N3,
+        );
+        let r = Ray::new(Point::new(-0.2, 0.3, -2.0), Vector::new(0.0, 0.0, 1.0));
+
+        for _ in 0..50_000 {
+            let xs = tri.intersect(r).unwrap();
+            let n = tri.normal_at(r.position(xs[0].t), Some(&xs[0]), None);
+
+            assert_eq!(n, Vector::new(-0.5547, 0.83205, 0.0));
+        }
+    }
+
+    #[test]
+    fn setting_the_vertex_normals_of_a_triangle_smooths_it() {
+        let mut tri = Triangle::new(Background::P1, Background::P2, Background::P3);
+        tri.n1 = Some(Background::N1);
+        tri.n2 = Some(Background::N2);
+        tri.n3 = Some(Background::N3);
+        let r = Ray::new(Point::new(-0.2, 0.3, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = tri.intersect(r).unwrap();
+        let n = tri.normal_at(r.position(xs[0].t), Some(&xs[0]), None);
+
+        assert_eq!(n, Vector::new(-0.5547, 0.83205, 0.0));
+    }
 }
@@ -1,7 +1,11 @@
+use std::any::Any;
+
 use super::Shape;
+use crate::id::new_id;
 #[allow(unused_imports)]
 use crate::Transformation;
-use crate::{Intersection, Material, Matrix, Point, Ray, Vector, IDENTITY};
+use crate::{Bounds, Intersection, Material, Matrix, Point, Ray, Vector, IDENTITY};
+use std::f64::consts::PI;
 use uuid::Uuid;
 
 /// A sphere is a three-dimensional solid figure which is perfectly round in
@@ -22,7 +26,7 @@ impl Sphere {
     /// Create a new `Sphere`.
     pub fn new() -> Self {
         Self {
-            id: Uuid::new_v4(),
+            id: new_id(),
             parent_id: None,
             transform: IDENTITY,
             material: Material::new(),
@@ -32,15 +36,9 @@ impl Sphere {
 
     /// Create a new glass `Sphere`
     pub fn glass_sphere() -> Self {
-        let mut m = Material::new();
-        m.refractive_index = 1.5;
-        m.transparency = 1.0;
         Self {
-            id: Uuid::new_v4(),
-            parent_id: None,
-            transform: IDENTITY,
-            material: m,
-            inherit_material: false,
+            material: Material::glass(),
+            ..Self::new()
         }
     }
 }
@@ -64,6 +62,10 @@ impl Shape for Sphere {
         self.parent_id = Some(id);
     }
 
+    fn clear_parent_id(&mut self) {
+        self.parent_id = None;
+    }
+
     fn transform(&self) -> Matrix {
         self.transform
     }
@@ -109,20 +111,45 @@ impl Shape for Sphere {
         }
     }
 
+    fn bounds(&self) -> Bounds {
+        Bounds::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0))
+    }
+
     fn local_normal_at(&self, object_point: Point, _hit: Option<&Intersection>) -> Vector {
         object_point - Point::new(0.0, 0.0, 0.0)
     }
 
+    /// Spherical mapping, `u` is the longitude and `v` the latitude of the
+    /// `point`.
+    fn uv_at(&self, point: Point) -> (f64, f64) {
+        // azimuthal angle around the y-axis, -π < theta <= π
+        let theta = point.x.atan2(point.z);
+        let radius = (point - Point::new(0.0, 0.0, 0.0)).magnitude();
+        // polar angle from the y-axis, 0 <= phi <= π
+        let phi = (point.y / radius).acos();
+        let raw_u = theta / (2.0 * PI);
+
+        // flip u so it increases counter-clockwise viewed from above
+        (1.0 - (raw_u + 0.5), 1.0 - phi / PI)
+    }
+
     fn inherit_material(&self) -> bool {
         self.inherit_material
     }
+
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Transformation, Vector};
-    use std::f64::consts::PI;
+    use crate::{float_eq, Transformation, Vector};
 
     // Chapter 5 Ray-Sphere Intersections
     // Page 59
@@ -316,4 +343,68 @@ mod tests {
         assert_eq!(s.material.transparency, 1.0);
         assert_eq!(s.material.refractive_index, 1.5);
     }
+
+    #[test]
+    fn repeatedly_intersecting_a_transformed_sphere() {
+        let mut s = Sphere::new();
+        s.transform = Transformation::new()
+            .scale(2.0, 2.0, 2.0)
+            .translate(0.0, 0.0, 1.0)
+            .build();
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        for _ in 0..100_000 {
+            let xs = s.intersect(r).expect("Expected hit, found none!");
+
+            assert_eq!(xs.len(), 2);
+            assert_eq!(xs[0].t, 4.0);
+            assert_eq!(xs[1].t, 8.0);
+        }
+
+        let n = s.normal_at(Point::new(0.0, 0.0, -1.0), None, None);
+        assert_eq!(n, Vector::new(0.0, 0.0, -1.0));
+    }
+
+    // Bonus Chapter Texture Mapping
+    #[test]
+    fn using_a_spherical_mapping_on_a_3d_point() {
+        let s = Sphere::new();
+        let data = vec![
+            (Point::new(0.0, 0.0, -1.0), (0.0, 0.5)),
+            (Point::new(1.0, 0.0, 0.0), (0.25, 0.5)),
+            (Point::new(0.0, 0.0, 1.0), (0.5, 0.5)),
+            (Point::new(-1.0, 0.0, 0.0), (0.75, 0.5)),
+            (Point::new(0.0, 1.0, 0.0), (0.5, 1.0)),
+            (Point::new(0.0, -1.0, 0.0), (0.5, 0.0)),
+            (
+                Point::new(2_f64.sqrt() / 2.0, 2_f64.sqrt() / 2.0, 0.0),
+                (0.25, 0.75),
+            ),
+        ];
+
+        for rec in data {
+            let (u, v) = s.uv_at(rec.0);
+            assert!(float_eq(u, rec.1 .0));
+            assert!(float_eq(v, rec.1 .1));
+        }
+    }
+
+    #[test]
+    fn the_spherical_mapping_depends_only_on_the_direction_of_the_point() {
+        let s = Sphere::new();
+        let data = vec![
+            Point::new(0.0, 0.0, -1.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(0.6, -0.48, 0.64),
+        ];
+
+        for p in data {
+            let scaled = Point::new(p.x * 2.5, p.y * 2.5, p.z * 2.5);
+            let (u1, v1) = s.uv_at(p);
+            let (u2, v2) = s.uv_at(scaled);
+            assert!(float_eq(u1, u2));
+            assert!(float_eq(v1, v2));
+        }
+    }
 }
@@ -1,7 +1,17 @@
 #[cfg(test)]
 use super::Shape;
 #[cfg(test)]
-use crate::{Intersection, Material, Matrix, Point, Ray, Vector, IDENTITY};
+use crate::id::new_id;
+#[cfg(test)]
+use crate::{Bounds, Intersection, Material, Matrix, Point, Ray, Vector, IDENTITY};
+#[cfg(test)]
+use std::{
+    any::Any,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
 #[cfg(test)]
 use uuid::Uuid;
 
@@ -12,22 +22,30 @@ pub struct TestShape {
     parent_id: Option<Uuid>,
     pub transform: Matrix,
     pub material: Material,
+    pub saved_ray: Mutex<Option<Ray>>,
+    pub bounds_calls: AtomicUsize,
 }
 
 #[cfg(test)]
 impl TestShape {
     pub fn new() -> TestShape {
         TestShape {
-            id: Uuid::new_v4(),
+            id: new_id(),
             parent_id: None,
             transform: IDENTITY,
             material: Material::new(),
+            saved_ray: Mutex::new(None),
+            bounds_calls: AtomicUsize::new(0),
         }
     }
 }
 
 #[cfg(test)]
 impl Shape for TestShape {
+    fn as_any(&self) -> Option<&dyn Any> {
+        Some(self)
+    }
+
     fn id(&self) -> Uuid {
         self.id
     }
@@ -40,6 +58,10 @@ impl Shape for TestShape {
         self.parent_id = Some(id);
     }
 
+    fn clear_parent_id(&mut self) {
+        self.parent_id = None;
+    }
+
     fn transform(&self) -> Matrix {
         self.transform
     }
@@ -61,6 +83,7 @@ impl Shape for TestShape {
     }
 
     fn local_intersect(&self, ray: Ray) -> Option<Vec<Intersection>> {
+        *self.saved_ray.lock().unwrap() = Some(ray);
         let t = ray.origin.x
             + ray.origin.y
             + ray.origin.z
@@ -70,6 +93,11 @@ impl Shape for TestShape {
         Some(vec![Intersection::new(t, self)])
     }
 
+    fn bounds(&self) -> Bounds {
+        self.bounds_calls.fetch_add(1, Ordering::Relaxed);
+        Bounds::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0))
+    }
+
     fn local_normal_at(&self, point: Point, _hit: Option<&Intersection>) -> Vector {
         Vector::new(point.x, point.y, point.z)
     }
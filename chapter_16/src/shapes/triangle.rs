@@ -1,6 +1,7 @@
 use std::any::Any;
 
-use crate::{Intersection, Material, Matrix, Point, Ray, Vector, EPSILON, IDENTITY};
+use crate::id::new_id;
+use crate::{Bounds, Intersection, Material, Matrix, Point, Ray, Vector, EPSILON, IDENTITY};
 use uuid::Uuid;
 
 use super::Shape;
@@ -17,16 +18,18 @@ pub struct Triangle {
     pub n1: Option<Vector>,
     pub n2: Option<Vector>,
     pub n3: Option<Vector>,
+    pub uv1: Option<(f64, f64)>,
+    pub uv2: Option<(f64, f64)>,
+    pub uv3: Option<(f64, f64)>,
     e1: Vector,
     e2: Vector,
     normal: Vector,
-    smooth_triangle: bool,
 }
 
 impl Triangle {
     pub fn new(p1: Point, p2: Point, p3: Point) -> Self {
         Triangle {
-            id: Uuid::new_v4(),
+            id: new_id(),
             parent_id: None,
             transform: IDENTITY,
             material: Material::new(),
@@ -36,10 +39,12 @@ impl Triangle {
             n1: None,
             n2: None,
             n3: None,
+            uv1: None,
+            uv2: None,
+            uv3: None,
             e1: p2 - p1,
             e2: p3 - p1,
             normal: (p3 - p1).cross(p2 - p1).normalize(),
-            smooth_triangle: false,
         }
     }
 
@@ -52,7 +57,7 @@ impl Triangle {
         n3: Vector,
     ) -> Self {
         Triangle {
-            id: Uuid::new_v4(),
+            id: new_id(),
             parent_id: None,
             transform: IDENTITY,
             material: Material::new(),
@@ -62,12 +67,150 @@ impl Triangle {
             n1: Some(n1),
             n2: Some(n2),
             n3: Some(n3),
+            uv1: None,
+            uv2: None,
+            uv3: None,
             e1: p2 - p1,
             e2: p3 - p1,
             normal: (p3 - p1).cross(p2 - p1).normalize(),
-            smooth_triangle: true,
         }
     }
+
+    /// Normal of the flat face of the triangle, the same everywhere on the
+    /// triangle regardless of any vertex normals.
+    pub fn face_normal(&self) -> Vector {
+        self.normal
+    }
+
+    /// The center of the triangle, the average of the vertices.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{shapes::Triangle, Point};
+    ///
+    /// let t = Triangle::new(
+    ///     Point::new(0.0, 3.0, 0.0),
+    ///     Point::new(-3.0, 0.0, 0.0),
+    ///     Point::new(3.0, 0.0, 3.0),
+    /// );
+    ///
+    /// assert_eq!(t.centroid(), Point::new(0.0, 1.0, 1.0));
+    /// ```
+    pub fn centroid(&self) -> Point {
+        Point::new(
+            (self.p1.x + self.p2.x + self.p3.x) / 3.0,
+            (self.p1.y + self.p2.y + self.p3.y) / 3.0,
+            (self.p1.z + self.p2.z + self.p3.z) / 3.0,
+        )
+    }
+
+    /// The area of the triangle, half of the magnitude of the cross product of
+    /// two of its edges.
+    pub fn area(&self) -> f64 {
+        self.e1.cross(self.e2).magnitude() / 2.0
+    }
+
+    /// Turn `self` into a smooth triangle using the normals `n1`, `n2` and `n3`
+    /// at the vertices `p1`, `p2` and `p3`.
+    pub fn set_normals(&mut self, n1: Vector, n2: Vector, n3: Vector) {
+        self.n1 = Some(n1);
+        self.n2 = Some(n2);
+        self.n3 = Some(n3);
+    }
+
+    /// Interpolate the texture coordinates of the vertices for the `u` and `v`
+    /// of the `hit`. Returns `None` if the triangle has no texture coordinates.
+    pub fn interpolate_uv(&self, hit: &Intersection) -> Option<(f64, f64)> {
+        let (uv1, uv2, uv3) = (self.uv1?, self.uv2?, self.uv3?);
+        let (u, v) = (hit.u?, hit.v?);
+        let w = 1.0 - u - v;
+
+        Some((
+            uv2.0 * u + uv3.0 * v + uv1.0 * w,
+            uv2.1 * u + uv3.1 * v + uv1.1 * w,
+        ))
+    }
+
+    /// Intersect the `ray` with every triangle in `tris` at once, giving the
+    /// same intersections as calling `local_intersect` on each triangle in
+    /// turn. The `ray` is in the space of the triangles, their transforms are
+    /// not used.
+    ///
+    /// The edges and first vertex of the triangles are copied into a separate
+    /// list for each of their `x`, `y`, and `z` so the Möller–Trumbore math is
+    /// the same simple loop for every triangle, which the compiler can turn
+    /// into SIMD instructions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{shapes::Triangle, Point, Ray, Vector};
+    ///
+    /// let tris = [
+    ///     Triangle::new(Point::new(0.0, 1.0, 0.0), Point::new(-1.0, 0.0, 0.0), Point::new(1.0, 0.0, 0.0)),
+    ///     Triangle::new(Point::new(0.0, 1.0, 2.0), Point::new(-1.0, 0.0, 2.0), Point::new(1.0, 0.0, 2.0)),
+    /// ];
+    /// let r = Ray::new(Point::new(0.0, 0.5, -2.0), Vector::new(0.0, 0.0, 1.0));
+    /// let xs = Triangle::intersect_batch(r, &tris);
+    ///
+    /// assert_eq!(xs.len(), 2);
+    /// assert_eq!(xs[0].t, 2.0);
+    /// assert_eq!(xs[1].t, 4.0);
+    /// ```
+    pub fn intersect_batch(ray: Ray, tris: &[Triangle]) -> Vec<Intersection<'_>> {
+        let column = |f: fn(&Triangle) -> f64| tris.iter().map(f).collect::<Vec<f64>>();
+        let (e1x, e1y, e1z) = (column(|t| t.e1.x), column(|t| t.e1.y), column(|t| t.e1.z));
+        let (e2x, e2y, e2z) = (column(|t| t.e2.x), column(|t| t.e2.y), column(|t| t.e2.z));
+        let (p1x, p1y, p1z) = (column(|t| t.p1.x), column(|t| t.p1.y), column(|t| t.p1.z));
+        let (o, d) = (ray.origin, ray.direction);
+
+        let mut det = vec![0.0; tris.len()];
+        let mut u = vec![0.0; tris.len()];
+        let mut v = vec![0.0; tris.len()];
+        let mut t = vec![0.0; tris.len()];
+        for i in 0..tris.len() {
+            let dir_cross_e2 = (
+                d.y * e2z[i] - d.z * e2y[i],
+                d.z * e2x[i] - d.x * e2z[i],
+                d.x * e2y[i] - d.y * e2x[i],
+            );
+            det[i] = e1x[i] * dir_cross_e2.0 + e1y[i] * dir_cross_e2.1 + e1z[i] * dir_cross_e2.2;
+
+            let f = 1.0 / det[i];
+            let p1_to_origin = (o.x - p1x[i], o.y - p1y[i], o.z - p1z[i]);
+            u[i] = f
+                * (p1_to_origin.0 * dir_cross_e2.0
+                    + p1_to_origin.1 * dir_cross_e2.1
+                    + p1_to_origin.2 * dir_cross_e2.2);
+
+            let origin_cross_e1 = (
+                p1_to_origin.1 * e1z[i] - p1_to_origin.2 * e1y[i],
+                p1_to_origin.2 * e1x[i] - p1_to_origin.0 * e1z[i],
+                p1_to_origin.0 * e1y[i] - p1_to_origin.1 * e1x[i],
+            );
+            v[i] =
+                f * (d.x * origin_cross_e1.0 + d.y * origin_cross_e1.1 + d.z * origin_cross_e1.2);
+            t[i] = f
+                * (e2x[i] * origin_cross_e1.0
+                    + e2y[i] * origin_cross_e1.1
+                    + e2z[i] * origin_cross_e1.2);
+        }
+
+        (0..tris.len())
+            .filter(|&i| {
+                det[i].abs() >= EPSILON
+                    && (0.0..=1.0).contains(&u[i])
+                    && v[i] >= 0.0
+                    && u[i] + v[i] <= 1.0
+            })
+            .map(|i| {
+                let mut x = Intersection::intersection_with_uv(t[i], &tris[i], u[i], v[i]);
+                x.backface = det[i] > 0.0;
+                x
+            })
+            .collect()
+    }
 }
 
 impl Shape for Triangle {
@@ -83,6 +226,10 @@ impl Shape for Triangle {
         self.parent_id = Some(id);
     }
 
+    fn clear_parent_id(&mut self) {
+        self.parent_id = None;
+    }
+
     fn transform(&self) -> Matrix {
         self.transform
     }
@@ -124,18 +271,44 @@ impl Shape for Triangle {
         }
 
         let t = f * self.e2.dot(origin_cross_e1);
-        Some(vec![Intersection::intersection_with_uv(t, self, u, v)])
+        let mut i = Intersection::intersection_with_uv(t, self, u, v);
+        // The determinant is the dot product of the ray direction and the
+        // normal, positive when the ray comes from behind the triangle.
+        i.backface = det > 0.0;
+        Some(vec![i])
+    }
+
+    fn bounds(&self) -> Bounds {
+        Bounds::empty()
+            .add_point(self.p1)
+            .add_point(self.p2)
+            .add_point(self.p3)
     }
 
     fn local_normal_at(&self, _point: Point, hit: Option<&Intersection>) -> Vector {
-        if self.smooth_triangle {
-            let hit = hit.unwrap();
-
-            self.n2.unwrap() * hit.u.unwrap()
-                + self.n3.unwrap() * hit.v.unwrap()
-                + self.n1.unwrap() * (1.0 - hit.u.unwrap() - hit.v.unwrap())
-        } else {
-            self.normal
+        match (
+            self.n1,
+            self.n2,
+            self.n3,
+            hit.and_then(|hit| hit.u.zip(hit.v)),
+        ) {
+            (Some(n1), Some(n2), Some(n3), Some((u, v))) => n2 * u + n3 * v + n1 * (1.0 - u - v),
+            _ => self.normal,
+        }
+    }
+
+    /// The texture coordinates interpolated from the vertices, or the `u` and
+    /// `v` of the `hit` itself when the triangle has no texture coordinates.
+    fn uv_at_hit(&self, point: Point, hit: Option<&Intersection>) -> (f64, f64) {
+        match hit {
+            Some(hit) => match self.interpolate_uv(hit) {
+                Some(uv) => uv,
+                None => match (hit.u, hit.v) {
+                    (Some(u), Some(v)) => (u, v),
+                    _ => self.uv_at(point),
+                },
+            },
+            None => self.uv_at(point),
         }
     }
 
@@ -146,11 +319,16 @@ impl Shape for Triangle {
     fn as_any(&self) -> Option<&dyn Any> {
         Some(self)
     }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any> {
+        Some(self)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::float_eq;
 
     // Chapter 15 Triangles
     // Page 208
@@ -261,5 +439,116 @@ mod tests {
 
         assert_eq!(xs.len(), 1);
         assert_eq!(xs[0].t, 2.0);
+        assert!(!xs[0].backface);
+    }
+
+    #[test]
+    fn a_ray_strikes_the_back_of_a_triangle() {
+        let t = Triangle::new(
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+        );
+        let r = Ray::new(Point::new(0.0, 0.5, 2.0), Vector::new(0.0, 0.0, -1.0));
+        let xs = t.local_intersect(r).unwrap();
+
+        assert_eq!(xs[0].t, 2.0);
+        assert!(xs[0].backface);
+
+        let comps = xs[0].prepare_computations(r, &xs, None);
+
+        assert!(comps.backface);
+    }
+
+    #[test]
+    fn a_triangle_uses_u_and_v_to_interpolate_texture_coordinates() {
+        let mut t = Triangle::new(
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+        );
+        t.uv1 = Some((0.5, 1.0));
+        t.uv2 = Some((0.0, 0.0));
+        t.uv3 = Some((1.0, 0.0));
+        let r = Ray::new(Point::new(-0.2, 0.3, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = t.local_intersect(r).unwrap();
+        let uv = t.interpolate_uv(&xs[0]).unwrap();
+
+        assert!(float_eq(uv.0, 0.4));
+        assert!(float_eq(uv.1, 0.3));
+    }
+
+    #[test]
+    fn a_triangle_has_a_bounding_box() {
+        let t = Triangle::new(
+            Point::new(-3.0, 7.0, 2.0),
+            Point::new(6.0, 2.0, -4.0),
+            Point::new(2.0, -1.0, -1.0),
+        );
+        let b = t.bounds();
+
+        assert_eq!(b.min, Point::new(-3.0, -1.0, -4.0));
+        assert_eq!(b.max, Point::new(6.0, 7.0, 2.0));
+    }
+
+    #[test]
+    fn intersecting_a_batch_of_triangles_matches_each_triangle() {
+        let mut tris = Vec::new();
+        for i in 0..8 {
+            let a = i as f64 * std::f64::consts::PI / 4.0;
+            let (s, c) = (a.sin(), a.cos());
+            tris.push(Triangle::new(
+                Point::new(-1.0 + 0.3 * c, -1.0, i as f64),
+                Point::new(1.0, -1.0 + 0.3 * s, i as f64 + c),
+                Point::new(0.2 * s, 1.5, i as f64 - s),
+            ));
+        }
+        let rays = [
+            Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0)),
+            Ray::new(Point::new(0.3, -0.4, 20.0), Vector::new(0.0, 0.0, -1.0)),
+            Ray::new(
+                Point::new(-4.0, 0.2, -3.0),
+                Vector::new(1.0, 0.0, 0.8).normalize(),
+            ),
+            Ray::new(Point::new(5.0, 5.0, 0.0), Vector::new(1.0, 0.0, 0.0)),
+        ];
+
+        for r in rays {
+            let expected: Vec<Intersection> = tris
+                .iter()
+                .filter_map(|t| t.local_intersect(r))
+                .flatten()
+                .collect();
+            let xs = Triangle::intersect_batch(r, &tris);
+
+            assert_eq!(xs.len(), expected.len());
+            for (x, e) in xs.iter().zip(expected.iter()) {
+                assert!(x.object.shape_eq(e.object));
+                assert_eq!((x.t, x.u, x.v), (e.t, e.u, e.v));
+                assert_eq!(x.backface, e.backface);
+            }
+        }
+    }
+
+    #[test]
+    fn the_centroid_of_a_triangle() {
+        let t = Triangle::new(
+            Point::new(1.0, 2.0, 3.0),
+            Point::new(4.0, -2.0, 0.0),
+            Point::new(-2.0, 3.0, 6.0),
+        );
+
+        assert_eq!(t.centroid(), Point::new(1.0, 1.0, 3.0));
+    }
+
+    #[test]
+    fn the_area_of_a_unit_right_triangle() {
+        let t = Triangle::new(
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Point::new(0.0, 1.0, 0.0),
+        );
+
+        assert_eq!(t.area(), 0.5);
     }
 }
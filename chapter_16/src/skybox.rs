@@ -0,0 +1,149 @@
+use crate::{Canvas, Color, Vector};
+
+/// An image based background surrounding the world. Six images, one for each
+/// face of a cube around the world, are mapped onto the cube with the same
+/// `u` and `v` texture coordinates as a [`crate::shapes::Cube`].
+#[derive(Debug)]
+pub struct Skybox {
+    /// Face in the `-x` direction.
+    pub left: Canvas,
+    /// Face in the `+x` direction.
+    pub right: Canvas,
+    /// Face in the `+y` direction.
+    pub up: Canvas,
+    /// Face in the `-y` direction.
+    pub down: Canvas,
+    /// Face in the `+z` direction.
+    pub front: Canvas,
+    /// Face in the `-z` direction.
+    pub back: Canvas,
+}
+
+impl Skybox {
+    /// Create a new skybox from the six faces of a cube.
+    pub fn new(
+        left: Canvas,
+        right: Canvas,
+        up: Canvas,
+        down: Canvas,
+        front: Canvas,
+        back: Canvas,
+    ) -> Skybox {
+        Skybox {
+            left,
+            right,
+            up,
+            down,
+            front,
+            back,
+        }
+    }
+
+    /// The color of the skybox seen looking in the direction `dir`. The face
+    /// is selected by the largest component of `dir`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Canvas, Color, Colors, Skybox, Vector};
+    ///
+    /// let face = |color| {
+    ///     let mut c = Canvas::new(1, 1);
+    ///     c.write_pixel(0, 0, color);
+    ///     c
+    /// };
+    /// let skybox = Skybox::new(
+    ///     face(Colors::RED),
+    ///     face(Colors::RED),
+    ///     face(Color::new(0.0, 0.0, 1.0)),
+    ///     face(Colors::BLACK),
+    ///     face(Colors::WHITE),
+    ///     face(Colors::BLACK),
+    /// );
+    /// let color = skybox.color_in_direction(Vector::new(0.0, 2.0, 1.0));
+    ///
+    /// assert_eq!(color, Color::new(0.0, 0.0, 1.0));
+    /// ```
+    pub fn color_in_direction(&self, dir: Vector) -> Color {
+        let coord = dir.x.abs().max(dir.y.abs()).max(dir.z.abs());
+        let (x, y, z) = (dir.x / coord, dir.y / coord, dir.z / coord);
+
+        let (face, u, v) = if x == 1.0 {
+            (&self.right, 1.0 - z, y + 1.0)
+        } else if x == -1.0 {
+            (&self.left, z + 1.0, y + 1.0)
+        } else if y == 1.0 {
+            (&self.up, x + 1.0, 1.0 - z)
+        } else if y == -1.0 {
+            (&self.down, x + 1.0, z + 1.0)
+        } else if z == 1.0 {
+            (&self.front, x + 1.0, y + 1.0)
+        } else {
+            (&self.back, 1.0 - x, y + 1.0)
+        };
+
+        face.pixel_at_uv(u.rem_euclid(2.0) / 2.0, v.rem_euclid(2.0) / 2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Colors;
+
+    // Each face is filled with `color` except for the top left corner which
+    // is white.
+    fn face(color: Color) -> Canvas {
+        let mut c = Canvas::new(2, 2);
+        for y in 0..2 {
+            for x in 0..2 {
+                c.write_pixel(x, y, color);
+            }
+        }
+        c.write_pixel(0, 0, Colors::WHITE);
+        c
+    }
+
+    fn skybox() -> Skybox {
+        Skybox::new(
+            face(Color::new(1.0, 0.0, 0.0)),
+            face(Color::new(0.0, 1.0, 0.0)),
+            face(Color::new(0.0, 0.0, 1.0)),
+            face(Color::new(1.0, 1.0, 0.0)),
+            face(Color::new(0.0, 1.0, 1.0)),
+            face(Color::new(1.0, 0.0, 1.0)),
+        )
+    }
+
+    #[test]
+    fn the_face_is_selected_by_the_largest_component() {
+        let skybox = skybox();
+        let data = vec![
+            (Vector::new(1.0, 0.0, 0.0), Color::new(0.0, 1.0, 0.0)),
+            (Vector::new(-1.0, 0.5, 0.0), Color::new(1.0, 0.0, 0.0)),
+            (Vector::new(0.0, 1.0, 0.0), Color::new(0.0, 0.0, 1.0)),
+            (Vector::new(0.5, -1.0, 0.0), Color::new(1.0, 1.0, 0.0)),
+            (Vector::new(0.0, 0.0, 1.0), Color::new(0.0, 1.0, 1.0)),
+            (Vector::new(0.0, 0.0, -3.0), Color::new(1.0, 0.0, 1.0)),
+        ];
+
+        for (dir, expected) in data {
+            assert_eq!(skybox.color_in_direction(dir), expected);
+        }
+    }
+
+    #[test]
+    fn the_corners_of_the_faces() {
+        let skybox = skybox();
+        let data = vec![
+            Vector::new(1.0, 0.99, 0.99),
+            Vector::new(-0.99, 1.0, -0.99),
+            Vector::new(-0.99, 0.99, 1.0),
+        ];
+
+        for dir in data {
+            assert_eq!(skybox.color_in_direction(dir), Colors::WHITE);
+            assert_ne!(skybox.color_in_direction(-dir), Colors::WHITE);
+        }
+    }
+}
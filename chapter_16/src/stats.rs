@@ -0,0 +1,76 @@
+//! Counts of how many times each shape is tested for an intersection with a
+//! ray. Only compiled with the `stats` feature.
+//!
+//! Each [`World`](crate::World) keeps its own counts, a test is counted for
+//! the world the shape is intersected through. The counts of the current
+//! world are found through a thread local set while the world intersects its
+//! objects, a shape intersected on its own is not counted.
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+};
+use uuid::Uuid;
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<Counters>>> = const { RefCell::new(None) };
+}
+
+/// The count of intersection tests of each shape in a world.
+#[derive(Debug, Default)]
+pub(crate) struct Counters {
+    counts: RwLock<HashMap<Uuid, AtomicUsize>>,
+}
+
+impl Counters {
+    /// Count one more intersection test for the shape with `id`.
+    fn record(&self, id: Uuid) {
+        if let Some(count) = self.counts.read().unwrap().get(&id) {
+            count.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        self.counts
+            .write()
+            .unwrap()
+            .entry(id)
+            .or_insert_with(|| AtomicUsize::new(0))
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The number of intersection tests of each shape tested.
+    pub(crate) fn counts(&self) -> HashMap<Uuid, usize> {
+        self.counts
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(id, count)| (*id, count.load(Ordering::Relaxed)))
+            .collect()
+    }
+
+    /// Forget the count of intersection tests of every shape.
+    pub(crate) fn reset(&self) {
+        self.counts.write().unwrap().clear();
+    }
+}
+
+/// Count one more intersection test for the shape with `id` in the counts of
+/// the world being intersected, if any.
+pub(crate) fn record_intersection(id: Uuid) {
+    CURRENT.with(|current| {
+        if let Some(counters) = current.borrow().as_ref() {
+            counters.record(id);
+        }
+    });
+}
+
+/// Call `f` with the intersection tests counted in `counters`.
+pub(crate) fn counting<T>(counters: &Arc<Counters>, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT.with(|current| current.replace(Some(Arc::clone(counters))));
+    let result = f();
+    CURRENT.with(|current| *current.borrow_mut() = previous);
+    result
+}
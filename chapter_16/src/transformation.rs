@@ -56,6 +56,49 @@ impl Transformation {
         }
     }
 
+    /// Start a transformation chain from an existing transformation
+    /// [`Matrix`], such as one returned by `view_transform`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Point, Transformation};
+    ///
+    /// let m = Transformation::new().translate(1.0, 0.0, 0.0).build();
+    /// let transform = Transformation::from_matrix(m)
+    ///     .scale(2.0, 2.0, 2.0)
+    ///     .build();
+    /// let p = Point::new(1.0, 1.0, 1.0);
+    ///
+    /// assert_eq!(transform * p, Point::new(4.0, 2.0, 2.0));
+    /// ```
+    pub fn from_matrix(m: Matrix) -> Transformation {
+        Transformation {
+            data: [m.row(0), m.row(1), m.row(2), m.row(3)],
+        }
+    }
+
+    /// Continue the chain of transformations with all of the transformations
+    /// of `other`, which are performed after the transformations of `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Point, Transformation};
+    ///
+    /// let move_right = Transformation::new().translate(1.0, 0.0, 0.0);
+    /// let grow = Transformation::new().scale(2.0, 2.0, 2.0);
+    /// let transform = move_right.then(grow).build();
+    /// let p = Point::new(1.0, 1.0, 1.0);
+    ///
+    /// assert_eq!(transform * p, Point::new(4.0, 2.0, 2.0));
+    /// ```
+    pub fn then(self, other: Transformation) -> Transformation {
+        Transformation {
+            data: multiple_array(other.data, self.data),
+        }
+    }
+
     /// Creates a matrix from the transformations data
     pub fn build(&self) -> Matrix {
         Matrix::new(self.data)
@@ -122,6 +165,73 @@ impl Transformation {
         }
     }
 
+    /// A transformation that scales all points of an object by the same amount
+    /// `s` along every axis.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Point, Transformation};
+    ///
+    /// let transform = Transformation::new()
+    ///     .scale_uniform(2.0)
+    ///     .build();
+    /// let p = Point::new(-4.0, 6.0, 8.0);
+    ///
+    /// assert_eq!(transform * p, Point::new(-8.0, 12.0, 16.0));
+    /// ```
+    pub fn scale_uniform(self, s: f64) -> Transformation {
+        self.scale(s, s, s)
+    }
+
+    /// A reflection across the `yz` plane, scaling by `-1` on the `x` axis.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Point, Transformation};
+    ///
+    /// let transform = Transformation::new().reflect_x().build();
+    /// let p = Point::new(2.0, 3.0, 4.0);
+    ///
+    /// assert_eq!(transform * p, Point::new(-2.0, 3.0, 4.0));
+    /// ```
+    pub fn reflect_x(self) -> Transformation {
+        self.scale(-1.0, 1.0, 1.0)
+    }
+
+    /// A reflection across the `xz` plane, scaling by `-1` on the `y` axis.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Point, Transformation};
+    ///
+    /// let transform = Transformation::new().reflect_y().build();
+    /// let p = Point::new(2.0, 3.0, 4.0);
+    ///
+    /// assert_eq!(transform * p, Point::new(2.0, -3.0, 4.0));
+    /// ```
+    pub fn reflect_y(self) -> Transformation {
+        self.scale(1.0, -1.0, 1.0)
+    }
+
+    /// A reflection across the `xy` plane, scaling by `-1` on the `z` axis.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::{Point, Transformation};
+    ///
+    /// let transform = Transformation::new().reflect_z().build();
+    /// let p = Point::new(2.0, 3.0, 4.0);
+    ///
+    /// assert_eq!(transform * p, Point::new(2.0, 3.0, -4.0));
+    /// ```
+    pub fn reflect_z(self) -> Transformation {
+        self.scale(1.0, 1.0, -1.0)
+    }
+
     /// Rotates an object around the `x` axis for the give number of radians
     ///
     /// # Example
@@ -548,4 +658,62 @@ mod tests {
 
         assert_eq!(t, e);
     }
+
+    #[test]
+    fn a_uniform_scaling_applied_to_a_point() {
+        let transform = Transformation::new().scale_uniform(2.0).build();
+        let p = Point::new(-4.0, 6.0, 8.0);
+
+        assert_eq!(transform * p, Point::new(-8.0, 12.0, 16.0));
+        assert_eq!(
+            transform,
+            Transformation::new().scale(2.0, 2.0, 2.0).build()
+        );
+    }
+
+    #[test]
+    fn reflecting_a_point_across_each_axis() {
+        let p = Point::new(2.0, 3.0, 4.0);
+
+        assert_eq!(
+            Transformation::new().reflect_x().build() * p,
+            Point::new(-2.0, 3.0, 4.0)
+        );
+        assert_eq!(
+            Transformation::new().reflect_y().build() * p,
+            Point::new(2.0, -3.0, 4.0)
+        );
+        assert_eq!(
+            Transformation::new().reflect_z().build() * p,
+            Point::new(2.0, 3.0, -4.0)
+        );
+    }
+
+    #[test]
+    fn composing_two_transformation_chains() {
+        let translate = Transformation::new().translate(5.0, -3.0, 2.0);
+        let scale = Transformation::new()
+            .scale(2.0, 3.0, 4.0)
+            .rotate_x(PI / 2.0);
+        let transform = translate.then(scale).build();
+        let p = Point::new(-3.0, 4.0, 5.0);
+
+        assert_eq!(transform, scale.build() * translate.build());
+        assert_eq!(transform * p, scale.build() * (translate.build() * p));
+    }
+
+    #[test]
+    fn continuing_a_chain_from_a_view_transformation() {
+        let from = Point::new(1.0, 3.0, 2.0);
+        let to = Point::new(4.0, -2.0, 8.0);
+        let up = Vector::new(1.0, 1.0, 0.0);
+        let view = Transformation::view_transform(from, to, up);
+        let transform = Transformation::from_matrix(view)
+            .translate(0.0, 0.0, -1.0)
+            .build();
+        let expected = Transformation::new().translate(0.0, 0.0, -1.0).build() * view;
+
+        assert_eq!(Transformation::from_matrix(view).build(), view);
+        assert_eq!(transform, expected);
+    }
 }
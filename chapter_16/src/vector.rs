@@ -40,6 +40,19 @@ impl Vector {
         Self { x, y, z }
     }
 
+    /// The `x`, `y` and `z` of the vector as an array.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rustic_ray::Vector;
+    ///
+    /// assert_eq!(Vector::new(1.0, 2.0, 3.0).as_array(), [1.0, 2.0, 3.0]);
+    /// ```
+    pub fn as_array(&self) -> [f64; 3] {
+        [self.x, self.y, self.z]
+    }
+
     /// Computes the length or the magnitude of `self`.
     ///
     /// # Example
@@ -214,6 +227,18 @@ impl Div<f64> for Vector {
     }
 }
 
+impl From<[f64; 3]> for Vector {
+    fn from(a: [f64; 3]) -> Self {
+        Vector::new(a[0], a[1], a[2])
+    }
+}
+
+impl From<Vector> for [f64; 3] {
+    fn from(p: Vector) -> Self {
+        p.as_array()
+    }
+}
+
 impl PartialEq for Vector {
     fn eq(&self, other: &Vector) -> bool {
         float_eq(self.x, other.x) && float_eq(self.y, other.y) && float_eq(self.z, other.z)
@@ -428,4 +453,14 @@ mod tests {
 
         assert_eq!(r, Vector::new(1.0, 0.0, 0.0));
     }
+
+    #[test]
+    fn converting_a_vector_to_and_from_an_array() {
+        let v = Vector::new(1.0, 2.0, 3.0);
+        let a: [f64; 3] = v.into();
+
+        assert_eq!(a, [1.0, 2.0, 3.0]);
+        assert_eq!(Vector::from(a), v);
+        assert_eq!(Vector::from(v.as_array()), v);
+    }
 }